@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::{dtos::TaskAction, repos::TaskRepository, errors::Error, logs::LogService};
+
+pub struct ArchiveService {
+    repo: Arc<dyn TaskRepository>,
+    log_service: Arc<LogService>,
+    auto_archive_days: i64 // 0 turns the automatic archiving off
+}
+
+impl ArchiveService {
+    pub fn new(repo: Arc<dyn TaskRepository>, log_service: Arc<LogService>, auto_archive_days: i64) -> ArchiveService {
+        ArchiveService { repo, log_service, auto_archive_days }
+    }
+
+    // Archiving is independent of the status, the task and its whole subtree just leave the default listings
+    pub async fn archive(&self, workspace_id: Uuid, task_id: Uuid, now: DateTime<Utc>) -> Result<Vec<Uuid>, Error> {
+        let ids = self.subtree(workspace_id, task_id).await?;
+
+        self.repo.update_archived(workspace_id, &ids, Some(now)).await?;
+        for id in &ids {
+            self.log_service.log_task_action(TaskAction::Archived, Some(*id), Some("TaskEntity"), None).await;
+        }
+
+        Ok(ids)
+    }
+
+    pub async fn unarchive(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<Uuid>, Error> {
+        let ids = self.subtree(workspace_id, task_id).await?;
+
+        self.repo.update_archived(workspace_id, &ids, None).await?;
+        for id in &ids {
+            self.log_service.log_task_action(TaskAction::Unarchived, Some(*id), Some("TaskEntity"), None).await;
+        }
+
+        Ok(ids)
+    }
+
+    // One pass of the archiving worker, returns the number of archived trees
+    pub async fn archive_done_trees(&self, now: DateTime<Utc>) -> usize {
+        if self.auto_archive_days <= 0 {
+            return 0;
+        }
+
+        let Some(done_before) = Duration::try_days(self.auto_archive_days).and_then(|d| now.checked_sub_signed(d)) else {
+            return 0;
+        };
+
+        let mut archived = 0;
+        for task in self.repo.get_done_trees_before(done_before).await {
+            if self.archive(task.workspace_id, task.id, now).await.is_ok() {
+                archived += 1;
+            }
+        }
+
+        archived
+    }
+
+    async fn subtree(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<Uuid>, Error> {
+        self.repo.get_by_id(workspace_id, task_id).await?;
+
+        let mut ids = vec![task_id];
+        ids.extend(self.repo.get_all_subtasks_recursive(workspace_id, task_id).await);
+
+        Ok(ids)
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use domain::models::AttachmentEntity;
+
+use chrono::Utc;
+use sha2::{Sha256, Digest};
+use uuid::Uuid;
+
+use crate::{dtos::{AttachmentDto, AttachmentContentDto, TaskAction}, repos::{AttachmentRepository, FileStorage, TaskRepository}, errors::Error, logs::LogService};
+
+pub struct AttachmentService {
+    repo: Arc<dyn AttachmentRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    files: Arc<dyn FileStorage>,
+    log_service: Arc<LogService>,
+    max_size: usize
+}
+
+impl AttachmentService {
+    pub fn new(repo: Arc<dyn AttachmentRepository>, task_repo: Arc<dyn TaskRepository>, files: Arc<dyn FileStorage>, log_service: Arc<LogService>, max_size: usize) -> AttachmentService {
+        AttachmentService { repo, task_repo, files, log_service, max_size }
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub async fn get_attachments(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<AttachmentDto>, Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let entities = self.repo.get_by_task(task_id).await;
+
+        Ok(entities.iter().map(AttachmentDto::new).collect())
+    }
+
+    pub async fn get_attachment_content(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid) -> Result<AttachmentContentDto, Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let entity = self.repo.get_by_id(task_id, id).await?;
+        let content = self.files.load(&entity.hash).await?;
+
+        Ok(AttachmentContentDto { file_name: entity.file_name, content_type: entity.content_type, content })
+    }
+
+    pub async fn add_attachment(&self, workspace_id: Uuid, task_id: Uuid, file_name: &str, content_type: &str, content: &[u8]) -> Result<Uuid, Error> {
+        if content.len() > self.max_size {
+            return Err(Error::TooLarge(format!("Attachment can't be larger than {} bytes", self.max_size)));
+        }
+
+        if file_name.trim().is_empty() {
+            return Err(Error::invalid_input("Attachment must have a file name"));
+        }
+
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        // The file must not be released between saving it and the attachment pointing at it
+        let hash = hex::encode(Sha256::digest(content));
+        let lock = self.repo.lock_hash(&hash).await?;
+        self.files.save(&hash, content).await?;
+
+        let id = Uuid::new_v4();
+        let entity = AttachmentEntity {
+            id,
+            task_id,
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            size: content.len() as i64,
+            hash,
+            create_date: Utc::now()
+        };
+
+        self.repo.insert(entity).await?;
+        lock.release().await;
+
+        self.log_service.log_task_action(TaskAction::AttachmentAdded, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(id)
+    }
+
+    pub async fn delete_attachment(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let entity = self.repo.get_by_id(task_id, id).await?;
+        self.repo.delete(task_id, id).await?;
+        self.release_files(&[entity.hash]).await;
+
+        self.log_service.log_task_action(TaskAction::AttachmentRemoved, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(())
+    }
+
+    // Hashes of the files attached to the task. Should be taken before the task removal
+    pub async fn get_task_hashes(&self, task_id: Uuid) -> Vec<String> {
+        self.repo.get_by_task(task_id).await.into_iter().map(|e| e.hash).collect()
+    }
+
+    // Same for every task of the workspace, taken before the workspace is deleted
+    pub async fn get_workspace_hashes(&self, workspace_id: Uuid) -> Vec<String> {
+        self.repo.get_hashes_by_workspace(workspace_id).await
+    }
+
+    // Removes files that are no longer referenced by any attachment. A file that can't be locked is kept
+    pub async fn release_files(&self, hashes: &[String]) {
+        for hash in hashes {
+            let Ok(lock) = self.repo.lock_hash(hash).await else {
+                continue;
+            };
+
+            if self.repo.count_by_hash(hash).await == 0 {
+                let _ = self.files.remove(hash).await;
+            }
+
+            lock.release().await;
+        }
+    }
+}
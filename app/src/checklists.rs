@@ -0,0 +1,88 @@
+use std::{sync::Arc, collections::HashSet};
+
+use domain::models::ChecklistItemEntity;
+
+use uuid::Uuid;
+
+use crate::{dtos::{ChecklistItemDto, CreateChecklistItemDto, ReorderChecklistDto, TaskAction}, repos::{ChecklistRepository, TaskRepository}, errors::Error, logs::LogService};
+
+pub struct ChecklistService {
+    repo: Arc<dyn ChecklistRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    log_service: Arc<LogService>
+}
+
+impl ChecklistService {
+    pub fn new(repo: Arc<dyn ChecklistRepository>, task_repo: Arc<dyn TaskRepository>, log_service: Arc<LogService>) -> ChecklistService {
+        ChecklistService { repo, task_repo, log_service }
+    }
+
+    pub async fn get_checklist(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<ChecklistItemDto>, Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let entities = self.repo.get_by_task(task_id).await;
+
+        Ok(entities.iter().map(ChecklistItemDto::new).collect())
+    }
+
+    pub async fn add_item(&self, workspace_id: Uuid, task_id: Uuid, details: &CreateChecklistItemDto) -> Result<Uuid, Error> {
+        if details.text.trim().is_empty() {
+            return Err(Error::invalid_input("Checklist item text can't be empty"));
+        }
+
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        // New items go to the end of the list
+        let position = self.repo.get_by_task(task_id).await.last().map_or(0, |i| i.position + 1);
+        let id = Uuid::new_v4();
+        let entity = ChecklistItemEntity {
+            id,
+            task_id,
+            text: details.text.clone(),
+            done: false,
+            position
+        };
+
+        self.repo.insert(entity).await?;
+        self.log_service.log_task_action(TaskAction::ChecklistItemAdded, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(id)
+    }
+
+    pub async fn toggle_item(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid) -> Result<ChecklistItemDto, Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let mut entity = self.repo.get_by_id(task_id, id).await?;
+        entity.done = !entity.done;
+
+        self.repo.set_done(task_id, id, entity.done).await?;
+        self.log_service.log_task_action(TaskAction::ChecklistItemToggled, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(ChecklistItemDto::new(&entity))
+    }
+
+    pub async fn reorder_items(&self, workspace_id: Uuid, task_id: Uuid, details: &ReorderChecklistDto) -> Result<(), Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        // The new order must mention every item of the checklist exactly once
+        let current: HashSet<Uuid> = self.repo.get_by_task(task_id).await.iter().map(|i| i.id).collect();
+        let requested: HashSet<Uuid> = details.item_ids.iter().copied().collect();
+        if requested.len() != details.item_ids.len() || requested != current {
+            return Err(Error::invalid_input("Item ids must list every checklist item exactly once"));
+        }
+
+        self.repo.set_positions(task_id, &details.item_ids).await?;
+        self.log_service.log_task_action(TaskAction::ChecklistReordered, Some(task_id), Some("TaskEntity"), None).await;
+
+        Ok(())
+    }
+
+    pub async fn remove_item(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        self.repo.delete(task_id, id).await?;
+        self.log_service.log_task_action(TaskAction::ChecklistItemRemoved, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(())
+    }
+}
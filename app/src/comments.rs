@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use domain::models::CommentEntity;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{dtos::{CommentDto, CommentRevisionDto, CreateCommentDto, UpdateCommentDto, TaskAction}, repos::{CommentRepository, TaskRepository}, errors::Error, logs::LogService, notifications::NotificationService};
+
+pub struct CommentService {
+    repo: Arc<dyn CommentRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    log_service: Arc<LogService>,
+    notification_service: Arc<NotificationService>
+}
+
+impl CommentService {
+    pub fn new(repo: Arc<dyn CommentRepository>, task_repo: Arc<dyn TaskRepository>, log_service: Arc<LogService>, notification_service: Arc<NotificationService>) -> CommentService {
+        CommentService { repo, task_repo, log_service, notification_service }
+    }
+
+    pub async fn get_comment_batch(&self, workspace_id: Uuid, task_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> Result<(Vec<CommentDto>, String), Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let (entities, ct) = self.repo.get_batch_by_task(task_id, continuation_token, take, descending).await;
+
+        Ok((entities.iter().map(CommentDto::new).collect(), ct))
+    }
+
+    pub async fn get_comment_revisions(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid) -> Result<Vec<CommentRevisionDto>, Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+        self.repo.get_by_id(task_id, id).await?;
+
+        let entities = self.repo.get_revisions(id).await;
+
+        Ok(entities.iter().map(CommentRevisionDto::new).collect())
+    }
+
+    pub async fn create_comment(&self, workspace_id: Uuid, task_id: Uuid, details: &CreateCommentDto) -> Result<Uuid, Error> {
+        validate_body(&details.body)?;
+        let task = self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let id = Uuid::new_v4();
+        let entity = CommentEntity {
+            id,
+            task_id,
+            author: details.author.clone(),
+            body: details.body.clone(),
+            create_date: Utc::now(),
+            update_date: None
+        };
+
+        self.repo.insert(entity).await?;
+        // Comments are logged against the task, so they show up in its action log
+        self.log_service.log_task_action(TaskAction::CommentAdded, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+        self.notification_service.notify_comment(&task, details.author.as_deref(), &details.body).await?;
+
+        Ok(id)
+    }
+
+    pub async fn update_comment(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid, details: &UpdateCommentDto) -> Result<(), Error> {
+        validate_body(&details.body)?;
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        self.repo.update(task_id, id, &details.body, Utc::now()).await?;
+        self.log_service.log_task_action(TaskAction::CommentEdited, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(())
+    }
+
+    pub async fn delete_comment(&self, workspace_id: Uuid, task_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        self.repo.delete(task_id, id).await?;
+        self.log_service.log_task_action(TaskAction::CommentDeleted, Some(task_id), Some("TaskEntity"), Some(&id.to_string())).await;
+
+        Ok(())
+    }
+}
+
+fn validate_body(body: &str) -> Result<(), Error> {
+    if body.trim().is_empty() {
+        return Err(Error::invalid_input("Comment can't be empty"));
+    }
+
+    Ok(())
+}
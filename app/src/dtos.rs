@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+
 use domain::{enums, models::LogEntity};
-use domain::models::{TaskEntity, TaskSearchEntity};
+use domain::models::{TaskEntity, TaskSearchEntity, WorkspaceEntity, TagEntity, CommentEntity, CommentRevisionEntity, AttachmentEntity, ChecklistItemEntity, RecurrenceEntity, ReminderEntity, NotificationPreferenceEntity, StatusEntity, PriorityEntity, TaskTreeNodeEntity, TemplateEntity, TrashItemEntity};
 
 use chrono::DateTime;
 use serde::{Serialize, Deserialize};
 
+use crate::errors::Error;
+
 
 #[derive(Serialize)]
 pub struct LogEntryDto {
@@ -15,22 +19,328 @@ pub struct LogEntryDto {
     payload: Option<String>
 }
 
+#[derive(Debug, Serialize)]
+pub struct WorkspaceDto {
+    id: String,
+    name: String,
+    create_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertWorkspaceDto {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagDto {
+    id: String,
+    name: String,
+    color: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertTagDto {
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentDto {
+    id: String,
+    task_id: String,
+    author: Option<String>,
+    body: String,
+    create_date: DateTime<chrono::Utc>,
+    update_date: Option<DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentRevisionDto {
+    id: String,
+    body: String,
+    create_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCommentDto {
+    pub author: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCommentDto {
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttachmentDto {
+    id: String,
+    task_id: String,
+    file_name: String,
+    content_type: String,
+    size: i64,
+    create_date: DateTime<chrono::Utc>,
+}
+
+// Downloaded file with metadata needed to serve it
+pub struct AttachmentContentDto {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChecklistItemDto {
+    id: String,
+    text: String,
+    done: bool,
+    position: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecurrenceDto {
+    rule: String,
+    clone_subtasks: bool,
+    occurrence: i32,
+    previous_task_id: Option<String>,
+    next_task_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertRecurrenceDto {
+    pub rule: String,
+    #[serde(default)]
+    pub clone_subtasks: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateDto {
+    id: String,
+    name: String,
+    create_date: DateTime<chrono::Utc>,
+    variables: Vec<String>, // placeholders used anywhere in the tree
+    root: TemplateNodeDto,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateSummaryDto {
+    id: String,
+    name: String,
+    create_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertTemplateDto {
+    pub name: String,
+    pub root: TemplateNodeDto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateNodeDto {
+    pub summary: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub priority: String, // name of a workspace priority
+    #[serde(default)]
+    pub due_in_days: i32, // counted from the instantiation date
+    #[serde(default)]
+    pub subtasks: Vec<TemplateNodeDto>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InstantiateTemplateDto {
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    pub date: Option<DateTime<chrono::Utc>>, // now when omitted
+    pub root_id: Option<uuid::Uuid>, // parent of the created tree, a new root task when omitted
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateTaskDto {
+    pub root_id: Option<uuid::Uuid>, // parent of the copy, it becomes a root task when omitted
+    #[serde(default)]
+    pub include_subtasks: bool,
+    #[serde(default)]
+    pub due_date_shift_days: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RollupDto {
+    pub auto_complete: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskProgressDto {
+    total: i64,
+    done: i64,
+    statuses: Vec<StatusCountDto>, // in the workspace ordering, statuses nobody uses are left out
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusCountDto {
+    status: String,
+    category: StatusCategory,
+    count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReminderDto {
+    pub id: String,
+    pub task_id: String,
+    pub workspace_id: String,
+    pub summary: String,
+    pub kind: String,
+    pub due_date: DateTime<chrono::Utc>,
+    pub fire_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssigneeDto {
+    pub assignee: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationPreferencesDto {
+    #[serde(default = "enabled")]
+    pub assignment: bool,
+    #[serde(default = "enabled")]
+    pub status_change: bool,
+    #[serde(default = "enabled")]
+    pub comments: bool,
+    #[serde(default = "enabled")]
+    pub due_soon: bool,
+    #[serde(default)]
+    pub digest: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusDto {
+    id: String,
+    name: String,
+    color: String,
+    position: i32,
+    category: StatusCategory,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertStatusDto {
+    pub name: String,
+    pub color: String,
+    pub position: Option<i32>, // goes last when omitted
+    pub category: StatusCategory,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PriorityDto {
+    id: String,
+    name: String,
+    color: String,
+    position: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertPriorityDto {
+    pub name: String,
+    pub color: String,
+    pub position: Option<i32>, // goes last when omitted
+}
+
+// Statuses are referred to by name
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkflowTransitionDto {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub required_fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkflowHookDto {
+    pub status: String,
+    pub hook: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkflowDto {
+    #[serde(default)]
+    pub transitions: Vec<WorkflowTransitionDto>, // empty means unrestricted
+    #[serde(default)]
+    pub hooks: Vec<WorkflowHookDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DueDateNodeDto {
+    id: String,
+    summary: String,
+    status: String,
+    due_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DueDateConflictDto {
+    parent_id: String,
+    child_id: String,
+    parent_due_date: DateTime<chrono::Utc>,
+    child_due_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DueDateChangeDto {
+    task_id: String,
+    current_due_date: DateTime<chrono::Utc>,
+    proposed_due_date: DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DueDateReportDto {
+    root_id: String,
+    conflicts: Vec<DueDateConflictDto>, // children due later than their parent
+    overdue: Vec<DueDateNodeDto>, // unfinished tasks past their due date
+    latest_chain: Vec<DueDateNodeDto>, // path from the root down to the task finishing last
+    latest_finish: DateTime<chrono::Utc>,
+    proposed_changes: Vec<DueDateChangeDto>, // what propagation would apply
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateChecklistItemDto {
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderChecklistDto {
+    pub item_ids: Vec<uuid::Uuid>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct TaskBaseDto {
     id: String,
     summary: String,
-    priority: TaskPriority,
-    status: TaskStatus,
+    priority: String,
+    status: String,
 }
 
 #[derive(Debug, Serialize)]
 pub struct TaskDetailedDto {
+    workspace_id: String,
+
     root_id: Option<String>,
 
     create_date: DateTime<chrono::Utc>,
 
     due_date: DateTime<chrono::Utc>,
 
+    assignee: Option<String>,
+
+    archived_at: Option<DateTime<chrono::Utc>>,
+
+    version: i32, // sent back in `If-Match` to make sure nobody changed the task in between
+
+    tags: Vec<TagDto>,
+
     #[serde(flatten)]
     base: TaskBaseDto,
 }
@@ -38,13 +348,41 @@ pub struct TaskDetailedDto {
 #[derive(Debug, Serialize)]
 pub struct TaskFullDto {
     root_task: Option<TaskBaseDto>,
+    breadcrumbs: Vec<TaskBaseDto>, // path from the top task down to the direct parent
     subtasks: Vec<TaskBaseDto>,
     description: Option<String>,
+    resolution: Option<String>,
+    checklist: Vec<ChecklistItemDto>,
+    checklist_completion: Option<f32>, // share of done items, none for an empty checklist
+    blockers: Vec<TaskBaseDto>, // tasks that have to be finished before this one
+    dependents: Vec<TaskBaseDto>, // tasks waiting for this one
+    recurrence: Option<RecurrenceDto>,
+    auto_complete: bool,
+    progress: TaskProgressDto, // statuses of the whole subtree, the task itself excluded
     
     #[serde(flatten)]
     detailed: TaskDetailedDto,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TaskTreeNodeDto {
+    child_count: i64, // can be more than `children` once the depth limit or the status filter cut them
+    children: Vec<TaskTreeNodeDto>,
+
+    #[serde(flatten)]
+    base: TaskBaseDto,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskTreeItemDto {
+    parent_id: Option<String>,
+    depth: i32,
+    child_count: i64,
+
+    #[serde(flatten)]
+    base: TaskBaseDto,
+}
+
 #[derive(Debug, Serialize)]
 pub struct TaskSearchDto {
     id: String,
@@ -55,40 +393,209 @@ pub struct TaskSearchDto {
 #[derive(Debug, Deserialize)]
 pub struct UpsertTaskDto {
     pub summary: String,
-    pub priority: TaskPriority,
-    pub status: TaskStatus,
+    pub priority: String, // name of a workspace priority
+    pub status: String, // name of a workspace status
     pub description: Option<String>,
-    pub due_date: DateTime<chrono::Utc>
+    pub due_date: DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub resolution: Option<String>, // kept as is when omitted
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum TaskPriority {
-    #[serde(rename = "Low")]
-    Low,
+// A JSON merge patch (RFC 7396) of a task, omitted fields are kept and null clears the ones that can be empty
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct TaskPatchDto {
+    #[serde(default, deserialize_with = "non_null")]
+    pub summary: Option<String>,
+    #[serde(default, deserialize_with = "non_null")]
+    pub priority: Option<String>, // name of a workspace priority
+    #[serde(default, deserialize_with = "non_null")]
+    pub status: Option<String>, // name of a workspace status
+    #[serde(default, deserialize_with = "non_null")]
+    pub description: Option<String>, // the column can't be null, "" empties it
+    #[serde(default, deserialize_with = "non_null")]
+    pub due_date: Option<DateTime<chrono::Utc>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub resolution: Option<Option<String>>,
+}
 
-    #[serde(rename = "Normal")]
-    Normal,
+// A field that may be left out but not set to null
+fn non_null<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
 
-    #[serde(rename = "High")]
-    High,
+// Precondition of a write on a task, taken from `If-Match`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpectedVersion {
+    Any, // "*", the task only has to exist
+    OneOf(Vec<i32>), // none of them matching fails the write
+}
 
-    #[serde(rename = "Urgent")]
-    Urgent,
+// Tells a field set to null (`Some(None)`) from a missing one (`None`)
+fn nullable<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Option<Option<T>>, D::Error> {
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub enum TaskStatus {
-    #[serde(rename = "Reserved")]
-    Reserved,
+pub enum StatusCategory {
+    #[serde(rename = "todo")]
+    Todo,
 
-    #[serde(rename = "Ongoing")]
-    Ongoing,
+    #[serde(rename = "in_progress")]
+    InProgress,
 
-    #[serde(rename = "Done")]
+    #[serde(rename = "done")]
     Done,
+}
+
+// What happens to the subtasks of a deleted task
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeleteMode {
+    #[serde(rename = "cascade")]
+    Cascade, // deleted along with it, the whole subtree
+
+    #[serde(rename = "reparent")]
+    Reparent, // moved to the parent of the deleted task
 
-    #[serde(rename = "Pending")]
-    Pending,
+    #[serde(rename = "orphan")]
+    Orphan, // become root tasks
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteSummaryDto {
+    mode: DeleteMode,
+    deleted: Vec<String>,
+    reparented: Vec<String>,
+    new_root_id: Option<String>, // where the reparented tasks went
+    orphaned: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum BulkMode {
+    #[default]
+    #[serde(rename = "all_or_nothing")]
+    AllOrNothing, // every operation is checked first, nothing is written unless all of them pass
+
+    #[serde(rename = "best_effort")]
+    BestEffort, // operations run one by one, a failed one doesn't stop the rest
+}
+
+// One item of a bulk request, `op` picks the kind
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op")]
+pub enum BulkOperationDto {
+    #[serde(rename = "update")]
+    Update {
+        id: uuid::Uuid,
+        version: Option<i32>, // the same check as `If-Match` of a single update
+        #[serde(flatten)]
+        details: TaskPatchDto // only the fields to change
+    },
+
+    #[serde(rename = "status")]
+    Status {
+        id: uuid::Uuid,
+        version: Option<i32>,
+        status: String,
+        #[serde(default)]
+        resolution: Option<String> // for workflows that require one
+    },
+
+    #[serde(rename = "priority")]
+    Priority {
+        id: uuid::Uuid,
+        version: Option<i32>,
+        priority: String
+    },
+
+    #[serde(rename = "move")]
+    Move {
+        id: uuid::Uuid,
+        version: Option<i32>,
+        root_id: Option<uuid::Uuid> // a root task when omitted
+    },
+
+    #[serde(rename = "delete")]
+    Delete {
+        id: uuid::Uuid,
+        version: Option<i32>,
+        mode: Option<DeleteMode> // "orphan" when omitted
+    },
+
+    #[serde(rename = "tag")]
+    Tag {
+        id: uuid::Uuid,
+        tag_id: uuid::Uuid
+    },
+
+    #[serde(rename = "untag")]
+    Untag {
+        id: uuid::Uuid,
+        tag_id: uuid::Uuid
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkRequestDto {
+    #[serde(default)]
+    pub mode: BulkMode,
+    pub deleted_by: Option<String>, // email, kept with the tasks deleted by the request
+    pub operations: Vec<BulkOperationDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub enum BulkItemStatus {
+    #[serde(rename = "ok")]
+    Ok,
+
+    #[serde(rename = "failed")]
+    Failed,
+
+    #[serde(rename = "skipped")]
+    Skipped, // not run because the request was rejected or stopped
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkItemResultDto {
+    index: usize,
+    id: String,
+    status: BulkItemStatus,
+    error: Option<String>, // kind of the failure, e.g. "not_found" or "invalid_transition"
+    message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkSummaryDto {
+    mode: BulkMode,
+    applied: bool, // false when an all-or-nothing request was rejected or stopped
+    succeeded: usize,
+    failed: usize,
+    results: Vec<BulkItemResultDto>,
+}
+
+// Exactly one sibling or none, without a sibling the task goes to the end of the subtasks of `root_id` (root tasks when omitted)
+#[derive(Debug, Deserialize)]
+pub struct MoveTaskDto {
+    pub root_id: Option<uuid::Uuid>, // has to match the parent of the sibling when both are given
+    pub before_id: Option<uuid::Uuid>,
+    pub after_id: Option<uuid::Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrashItemDto {
+    parent_id: Option<String>, // where the task is restored to while that parent is still around
+    deleted_at: DateTime<chrono::Utc>,
+    deleted_by: Option<String>,
+    subtask_count: i64,
+
+    #[serde(flatten)]
+    base: TaskBaseDto,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreSummaryDto {
+    restored: Vec<String>,
+    root_id: Option<String>, // none when the original parent is gone and the task came back as a root task
+    reattached: Vec<String>, // subtasks taken back from where the delete had put them
 }
 
 #[derive(Debug, Serialize)]
@@ -104,44 +611,100 @@ pub enum TaskAction {
 
     #[serde(rename = "RootChanged")]
     RootChanged,
-}
 
-impl TaskPriority {
-    fn new(source: &enums::TaskPriority) -> Self {
-        match source {
-            enums::TaskPriority::Low => TaskPriority::Low,
-            enums::TaskPriority::Normal => TaskPriority::Normal,
-            enums::TaskPriority::High => TaskPriority::High,
-            enums::TaskPriority::Urgent => TaskPriority::Urgent
-        }
-    }
+    #[serde(rename = "TagAdded")]
+    TagAdded,
 
-    pub fn as_model(&self) -> enums::TaskPriority {
-        match self {
-            TaskPriority::Low => enums::TaskPriority::Low,
-            TaskPriority::Normal => enums::TaskPriority::Normal,
-            TaskPriority::High => enums::TaskPriority::High,
-            TaskPriority::Urgent => enums::TaskPriority::Urgent
-        }
-    }
+    #[serde(rename = "TagRemoved")]
+    TagRemoved,
+
+    #[serde(rename = "CommentAdded")]
+    CommentAdded,
+
+    #[serde(rename = "CommentEdited")]
+    CommentEdited,
+
+    #[serde(rename = "CommentDeleted")]
+    CommentDeleted,
+
+    #[serde(rename = "AttachmentAdded")]
+    AttachmentAdded,
+
+    #[serde(rename = "AttachmentRemoved")]
+    AttachmentRemoved,
+
+    #[serde(rename = "ChecklistItemAdded")]
+    ChecklistItemAdded,
+
+    #[serde(rename = "ChecklistItemToggled")]
+    ChecklistItemToggled,
+
+    #[serde(rename = "ChecklistItemRemoved")]
+    ChecklistItemRemoved,
+
+    #[serde(rename = "ChecklistReordered")]
+    ChecklistReordered,
+
+    #[serde(rename = "DependencyAdded")]
+    DependencyAdded,
+
+    #[serde(rename = "DependencyRemoved")]
+    DependencyRemoved,
+
+    #[serde(rename = "DueDateAdjusted")]
+    DueDateAdjusted,
+
+    #[serde(rename = "RecurrenceChanged")]
+    RecurrenceChanged,
+
+    #[serde(rename = "RecurrenceGenerated")]
+    RecurrenceGenerated,
+
+    #[serde(rename = "ReminderFired")]
+    ReminderFired,
+
+    #[serde(rename = "AssigneeChanged")]
+    AssigneeChanged,
+
+    #[serde(rename = "StatusChanged")]
+    StatusChanged,
+
+    #[serde(rename = "StatusRolledUp")]
+    StatusRolledUp,
+
+    #[serde(rename = "Restored")]
+    Restored,
+
+    #[serde(rename = "Purged")]
+    Purged,
+
+    #[serde(rename = "Archived")]
+    Archived,
+
+    #[serde(rename = "Unarchived")]
+    Unarchived,
+
+    #[serde(rename = "Reordered")]
+    Reordered,
+
+    #[serde(rename = "ReminderFailed")]
+    ReminderFailed,
 }
 
-impl TaskStatus {
-    fn new(source: &enums::TaskStatus) -> Self {
+impl StatusCategory {
+    fn new(source: &enums::StatusCategory) -> Self {
         match source {
-            enums::TaskStatus::Done => TaskStatus::Done,
-            enums::TaskStatus::Ongoing => TaskStatus::Ongoing,
-            enums::TaskStatus::Pending => TaskStatus::Pending,
-            enums::TaskStatus::Reserved => TaskStatus::Reserved
+            enums::StatusCategory::Todo => StatusCategory::Todo,
+            enums::StatusCategory::InProgress => StatusCategory::InProgress,
+            enums::StatusCategory::Done => StatusCategory::Done
         }
     }
 
-    pub fn as_model(&self) -> enums::TaskStatus {
+    pub fn as_model(&self) -> enums::StatusCategory {
         match self {
-            TaskStatus::Done => enums::TaskStatus::Done,
-            TaskStatus::Ongoing => enums::TaskStatus::Ongoing,
-            TaskStatus::Pending => enums::TaskStatus::Pending,
-            TaskStatus::Reserved => enums::TaskStatus::Reserved
+            StatusCategory::Todo => enums::StatusCategory::Todo,
+            StatusCategory::InProgress => enums::StatusCategory::InProgress,
+            StatusCategory::Done => enums::StatusCategory::Done
         }
     }
 }
@@ -152,7 +715,33 @@ impl TaskAction {
             enums::TaskAction::Create => TaskAction::Create,
             enums::TaskAction::Delete => TaskAction::Delete,
             enums::TaskAction::Update => TaskAction::Update,
-            enums::TaskAction::RootChanged => TaskAction::RootChanged
+            enums::TaskAction::RootChanged => TaskAction::RootChanged,
+            enums::TaskAction::TagAdded => TaskAction::TagAdded,
+            enums::TaskAction::TagRemoved => TaskAction::TagRemoved,
+            enums::TaskAction::CommentAdded => TaskAction::CommentAdded,
+            enums::TaskAction::CommentEdited => TaskAction::CommentEdited,
+            enums::TaskAction::CommentDeleted => TaskAction::CommentDeleted,
+            enums::TaskAction::AttachmentAdded => TaskAction::AttachmentAdded,
+            enums::TaskAction::AttachmentRemoved => TaskAction::AttachmentRemoved,
+            enums::TaskAction::ChecklistItemAdded => TaskAction::ChecklistItemAdded,
+            enums::TaskAction::ChecklistItemToggled => TaskAction::ChecklistItemToggled,
+            enums::TaskAction::ChecklistItemRemoved => TaskAction::ChecklistItemRemoved,
+            enums::TaskAction::ChecklistReordered => TaskAction::ChecklistReordered,
+            enums::TaskAction::DependencyAdded => TaskAction::DependencyAdded,
+            enums::TaskAction::DependencyRemoved => TaskAction::DependencyRemoved,
+            enums::TaskAction::DueDateAdjusted => TaskAction::DueDateAdjusted,
+            enums::TaskAction::RecurrenceChanged => TaskAction::RecurrenceChanged,
+            enums::TaskAction::RecurrenceGenerated => TaskAction::RecurrenceGenerated,
+            enums::TaskAction::ReminderFired => TaskAction::ReminderFired,
+            enums::TaskAction::AssigneeChanged => TaskAction::AssigneeChanged,
+            enums::TaskAction::StatusChanged => TaskAction::StatusChanged,
+            enums::TaskAction::StatusRolledUp => TaskAction::StatusRolledUp,
+            enums::TaskAction::Restored => TaskAction::Restored,
+            enums::TaskAction::Purged => TaskAction::Purged,
+            enums::TaskAction::Archived => TaskAction::Archived,
+            enums::TaskAction::Unarchived => TaskAction::Unarchived,
+            enums::TaskAction::Reordered => TaskAction::Reordered,
+            enums::TaskAction::ReminderFailed => TaskAction::ReminderFailed
         }
     }
 
@@ -161,7 +750,100 @@ impl TaskAction {
             TaskAction::Create => enums::TaskAction::Create,
             TaskAction::Delete => enums::TaskAction::Delete,
             TaskAction::Update => enums::TaskAction::Update,
-            TaskAction::RootChanged => enums::TaskAction::RootChanged
+            TaskAction::RootChanged => enums::TaskAction::RootChanged,
+            TaskAction::TagAdded => enums::TaskAction::TagAdded,
+            TaskAction::TagRemoved => enums::TaskAction::TagRemoved,
+            TaskAction::CommentAdded => enums::TaskAction::CommentAdded,
+            TaskAction::CommentEdited => enums::TaskAction::CommentEdited,
+            TaskAction::CommentDeleted => enums::TaskAction::CommentDeleted,
+            TaskAction::AttachmentAdded => enums::TaskAction::AttachmentAdded,
+            TaskAction::AttachmentRemoved => enums::TaskAction::AttachmentRemoved,
+            TaskAction::ChecklistItemAdded => enums::TaskAction::ChecklistItemAdded,
+            TaskAction::ChecklistItemToggled => enums::TaskAction::ChecklistItemToggled,
+            TaskAction::ChecklistItemRemoved => enums::TaskAction::ChecklistItemRemoved,
+            TaskAction::ChecklistReordered => enums::TaskAction::ChecklistReordered,
+            TaskAction::DependencyAdded => enums::TaskAction::DependencyAdded,
+            TaskAction::DependencyRemoved => enums::TaskAction::DependencyRemoved,
+            TaskAction::DueDateAdjusted => enums::TaskAction::DueDateAdjusted,
+            TaskAction::RecurrenceChanged => enums::TaskAction::RecurrenceChanged,
+            TaskAction::RecurrenceGenerated => enums::TaskAction::RecurrenceGenerated,
+            TaskAction::ReminderFired => enums::TaskAction::ReminderFired,
+            TaskAction::AssigneeChanged => enums::TaskAction::AssigneeChanged,
+            TaskAction::StatusChanged => enums::TaskAction::StatusChanged,
+            TaskAction::StatusRolledUp => enums::TaskAction::StatusRolledUp,
+            TaskAction::Restored => enums::TaskAction::Restored,
+            TaskAction::Purged => enums::TaskAction::Purged,
+            TaskAction::Archived => enums::TaskAction::Archived,
+            TaskAction::Unarchived => enums::TaskAction::Unarchived,
+            TaskAction::Reordered => enums::TaskAction::Reordered,
+            TaskAction::ReminderFailed => enums::TaskAction::ReminderFailed
+        }
+    }
+}
+
+impl WorkspaceDto {
+    pub fn new(entity: &WorkspaceEntity) -> Self {
+        WorkspaceDto {
+            id: entity.id.to_string(),
+            name: entity.name.clone(),
+            create_date: entity.create_date
+        }
+    }
+}
+
+impl TagDto {
+    pub fn new(entity: &TagEntity) -> Self {
+        TagDto {
+            id: entity.id.to_string(),
+            name: entity.name.clone(),
+            color: entity.color.clone()
+        }
+    }
+}
+
+impl CommentDto {
+    pub fn new(entity: &CommentEntity) -> Self {
+        CommentDto {
+            id: entity.id.to_string(),
+            task_id: entity.task_id.to_string(),
+            author: entity.author.clone(),
+            body: entity.body.clone(),
+            create_date: entity.create_date,
+            update_date: entity.update_date
+        }
+    }
+}
+
+impl CommentRevisionDto {
+    pub fn new(entity: &CommentRevisionEntity) -> Self {
+        CommentRevisionDto {
+            id: entity.id.to_string(),
+            body: entity.body.clone(),
+            create_date: entity.create_date
+        }
+    }
+}
+
+impl AttachmentDto {
+    pub fn new(entity: &AttachmentEntity) -> Self {
+        AttachmentDto {
+            id: entity.id.to_string(),
+            task_id: entity.task_id.to_string(),
+            file_name: entity.file_name.clone(),
+            content_type: entity.content_type.clone(),
+            size: entity.size,
+            create_date: entity.create_date
+        }
+    }
+}
+
+impl ChecklistItemDto {
+    pub fn new(entity: &ChecklistItemEntity) -> Self {
+        ChecklistItemDto {
+            id: entity.id.to_string(),
+            text: entity.text.clone(),
+            done: entity.done,
+            position: entity.position
         }
     }
 }
@@ -171,30 +853,179 @@ impl TaskBaseDto {
         TaskBaseDto {
             id: entity.id.to_string(),
             summary: entity.summary.clone(),
-            priority: TaskPriority::new(&entity.priority),
-            status: TaskStatus::new(&entity.status)
+            priority: entity.priority.name.clone(),
+            status: entity.status.name.clone()
         }
     }
 }
 
-impl TaskDetailedDto {
+impl RecurrenceDto {
+    pub fn new(entity: &RecurrenceEntity) -> Self {
+        RecurrenceDto {
+            rule: entity.rule.clone(),
+            clone_subtasks: entity.clone_subtasks,
+            occurrence: entity.occurrence,
+            previous_task_id: entity.previous_task_id.map(|id| id.to_string()),
+            next_task_id: entity.next_task_id.map(|id| id.to_string())
+        }
+    }
+}
+
+impl ReminderDto {
+    pub fn new(entity: &ReminderEntity, task: &TaskEntity) -> Self {
+        ReminderDto {
+            id: entity.id.to_string(),
+            task_id: task.id.to_string(),
+            workspace_id: task.workspace_id.to_string(),
+            summary: task.summary.clone(),
+            kind: entity.kind.clone(),
+            due_date: entity.due_date,
+            fire_date: entity.fire_date
+        }
+    }
+}
+
+impl NotificationPreferencesDto {
+    pub fn new(entity: &NotificationPreferenceEntity) -> Self {
+        NotificationPreferencesDto {
+            assignment: entity.assignment,
+            status_change: entity.status_change,
+            comments: entity.comments,
+            due_soon: entity.due_soon,
+            digest: entity.digest
+        }
+    }
+}
+
+impl StatusDto {
+    pub fn new(entity: &StatusEntity) -> Self {
+        StatusDto {
+            id: entity.id.to_string(),
+            name: entity.name.clone(),
+            color: entity.color.clone(),
+            position: entity.position,
+            category: StatusCategory::new(&entity.category)
+        }
+    }
+}
+
+impl PriorityDto {
+    pub fn new(entity: &PriorityEntity) -> Self {
+        PriorityDto {
+            id: entity.id.to_string(),
+            name: entity.name.clone(),
+            color: entity.color.clone(),
+            position: entity.position
+        }
+    }
+}
+
+impl DueDateNodeDto {
     pub fn new(entity: &TaskEntity) -> Self {
+        DueDateNodeDto {
+            id: entity.id.to_string(),
+            summary: entity.summary.clone(),
+            status: entity.status.name.clone(),
+            due_date: entity.due_date
+        }
+    }
+}
+
+impl DueDateConflictDto {
+    pub fn new(parent: &TaskEntity, child: &TaskEntity) -> Self {
+        DueDateConflictDto {
+            parent_id: parent.id.to_string(),
+            child_id: child.id.to_string(),
+            parent_due_date: parent.due_date,
+            child_due_date: child.due_date
+        }
+    }
+}
+
+impl DueDateChangeDto {
+    pub fn new(entity: &TaskEntity, proposed_due_date: DateTime<chrono::Utc>) -> Self {
+        DueDateChangeDto {
+            task_id: entity.id.to_string(),
+            current_due_date: entity.due_date,
+            proposed_due_date
+        }
+    }
+}
+
+impl DueDateReportDto {
+    pub fn new(root: &TaskEntity, conflicts: Vec<DueDateConflictDto>, overdue: Vec<DueDateNodeDto>, latest_chain: Vec<DueDateNodeDto>, proposed_changes: Vec<DueDateChangeDto>) -> Self {
+        DueDateReportDto {
+            root_id: root.id.to_string(),
+            latest_finish: latest_chain.last().map_or(root.due_date, |n| n.due_date),
+            conflicts,
+            overdue,
+            latest_chain,
+            proposed_changes
+        }
+    }
+}
+
+impl TaskDetailedDto {
+    pub fn new(entity: &TaskEntity, tags: &[TagEntity]) -> Self {
         TaskDetailedDto {
-            root_id: entity.root_task_id.map_or(None, |u| Some(u.to_string())),
+            workspace_id: entity.workspace_id.to_string(),
+            root_id: entity.root_task_id.map(|u| u.to_string()),
             create_date: entity.create_date,
             due_date: entity.due_date,
+            assignee: entity.assignee.clone(),
+            archived_at: entity.archived_at,
+            version: entity.version,
+            tags: tags.iter().map(TagDto::new).collect(),
             base: TaskBaseDto::new(entity)
         }
     }
 }
 
 impl TaskFullDto {
-    pub fn new(entity: &TaskEntity, root_entity: Option<&TaskEntity>, subtasks: &Vec<TaskEntity>) -> Self {
+    pub fn new(entity: &TaskEntity, root_entity: Option<&TaskEntity>, subtasks: &[TaskEntity], tags: &[TagEntity], checklist: &[ChecklistItemEntity], blockers: &[TaskEntity], dependents: &[TaskEntity]) -> Self {
+        let done_count = checklist.iter().filter(|i| i.done).count();
+
         TaskFullDto {
-            root_task: root_entity.map_or(None, |e| Some(TaskBaseDto::new(e))),
-            subtasks: subtasks.iter().map(|e| TaskBaseDto::new(e)).collect(),
+            root_task: root_entity.map(TaskBaseDto::new),
+            breadcrumbs: vec![],
+            subtasks: subtasks.iter().map(TaskBaseDto::new).collect(),
             description: entity.description.clone(),
-            detailed: TaskDetailedDto::new(entity)
+            resolution: entity.resolution.clone(),
+            checklist: checklist.iter().map(ChecklistItemDto::new).collect(),
+            checklist_completion: if checklist.is_empty() { None } else { Some(done_count as f32 / checklist.len() as f32) },
+            blockers: blockers.iter().map(TaskBaseDto::new).collect(),
+            dependents: dependents.iter().map(TaskBaseDto::new).collect(),
+            recurrence: None,
+            auto_complete: entity.auto_complete,
+            progress: TaskProgressDto::new(&[]),
+            detailed: TaskDetailedDto::new(entity, tags)
+        }
+    }
+
+    pub fn with_breadcrumbs(mut self, ancestors: &[TaskEntity]) -> Self {
+        self.breadcrumbs = ancestors.iter().map(TaskBaseDto::new).collect();
+        self
+    }
+
+    pub fn with_recurrence(mut self, recurrence: Option<&RecurrenceEntity>) -> Self {
+        self.recurrence = recurrence.map(RecurrenceDto::new);
+        self
+    }
+
+    pub fn with_progress(mut self, status_counts: &[(StatusEntity, i64)]) -> Self {
+        self.progress = TaskProgressDto::new(status_counts);
+        self
+    }
+
+    pub fn version(&self) -> i32 { self.detailed.version }
+}
+
+impl TaskProgressDto {
+    pub fn new(status_counts: &[(StatusEntity, i64)]) -> Self {
+        TaskProgressDto {
+            total: status_counts.iter().map(|(_, count)| count).sum(),
+            done: status_counts.iter().filter(|(s, _)| s.category == enums::StatusCategory::Done).map(|(_, count)| count).sum(),
+            statuses: status_counts.iter().map(|(s, count)| StatusCountDto { status: s.name.clone(), category: StatusCategory::new(&s.category), count: *count }).collect()
         }
     }
 }
@@ -205,13 +1036,177 @@ impl LogEntryDto {
             id: entity.id.to_string(),
             action: TaskAction::new(&entity.action),
             timestamp: entity.timestamp,
-            entity_id: entity.entity_id.map_or(None, |uuid| Some(uuid.to_string())),
+            entity_id: entity.entity_id.map(|uuid| uuid.to_string()),
             entity_type: entity.entity_type.clone(),
             payload: entity.payload.clone()
         }
     }
 }
 
+impl TaskTreeNodeDto {
+    pub fn new(entity: &TaskTreeNodeEntity, children: Vec<TaskTreeNodeDto>) -> Self {
+        TaskTreeNodeDto {
+            child_count: entity.child_count,
+            children,
+            base: TaskBaseDto::new(&entity.task)
+        }
+    }
+}
+
+impl TaskTreeItemDto {
+    pub fn new(entity: &TaskTreeNodeEntity) -> Self {
+        TaskTreeItemDto {
+            // The top node is reported without a parent even when it has one
+            parent_id: if entity.depth == 0 { None } else { entity.task.root_task_id.map(|id| id.to_string()) },
+            depth: entity.depth,
+            child_count: entity.child_count,
+            base: TaskBaseDto::new(&entity.task)
+        }
+    }
+}
+
+impl DeleteSummaryDto {
+    // `moved` are the former direct subtasks, they are orphans unless there is a new root
+    pub fn new(mode: DeleteMode, deleted: &[uuid::Uuid], moved: &[uuid::Uuid], new_root_id: Option<uuid::Uuid>) -> Self {
+        let to_strings = |ids: &[uuid::Uuid]| ids.iter().map(|id| id.to_string()).collect();
+
+        DeleteSummaryDto {
+            mode,
+            deleted: to_strings(deleted),
+            reparented: if new_root_id.is_some() { to_strings(moved) } else { vec![] },
+            new_root_id: new_root_id.map(|id| id.to_string()),
+            orphaned: if new_root_id.is_some() { vec![] } else { to_strings(moved) }
+        }
+    }
+}
+
+impl TaskPatchDto {
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_none()
+            && self.priority.is_none()
+            && self.status.is_none()
+            && self.description.is_none()
+            && self.due_date.is_none()
+            && self.resolution.is_none()
+    }
+}
+
+impl BulkOperationDto {
+    // Tag operations don't touch the task row, so they carry no version
+    pub fn expected_version(&self) -> Option<ExpectedVersion> {
+        let version = match self {
+            BulkOperationDto::Update { version, .. } => version,
+            BulkOperationDto::Status { version, .. } => version,
+            BulkOperationDto::Priority { version, .. } => version,
+            BulkOperationDto::Move { version, .. } => version,
+            BulkOperationDto::Delete { version, .. } => version,
+            BulkOperationDto::Tag { .. } => &None,
+            BulkOperationDto::Untag { .. } => &None
+        };
+
+        version.map(|v| ExpectedVersion::OneOf(vec![v]))
+    }
+
+    pub fn id(&self) -> uuid::Uuid {
+        match self {
+            BulkOperationDto::Update { id, .. } => *id,
+            BulkOperationDto::Status { id, .. } => *id,
+            BulkOperationDto::Priority { id, .. } => *id,
+            BulkOperationDto::Move { id, .. } => *id,
+            BulkOperationDto::Delete { id, .. } => *id,
+            BulkOperationDto::Tag { id, .. } => *id,
+            BulkOperationDto::Untag { id, .. } => *id
+        }
+    }
+}
+
+impl BulkItemResultDto {
+    pub fn new(index: usize, id: uuid::Uuid, status: BulkItemStatus, error: Option<&Error>) -> Self {
+        let (kind, message) = match error {
+            Some(Error::EntityNotFound(m)) => (Some("not_found"), Some(m.clone())),
+            Some(Error::InvalidRootBinding(m)) => (Some("invalid_root_binding"), Some(m.clone())),
+            Some(Error::InvalidInput(m)) => (Some("invalid_input"), Some(m.clone())),
+            Some(Error::TooLarge(m)) => (Some("too_large"), Some(m.clone())),
+            Some(Error::Conflict(m)) => (Some("conflict"), Some(m.clone())),
+            Some(Error::InvalidTransition(m)) => (Some("invalid_transition"), Some(m.clone())),
+            Some(Error::PreconditionFailed(m)) => (Some("precondition_failed"), Some(m.clone())),
+            Some(Error::PreconditionRequired(m)) => (Some("precondition_required"), Some(m.clone())),
+            Some(err) => (Some("error"), Some(format!("{:?}", err))),
+            None => (None, None)
+        };
+
+        BulkItemResultDto {
+            index,
+            id: id.to_string(),
+            status,
+            error: kind.map(|k| k.to_string()),
+            message
+        }
+    }
+
+    pub fn is_ok(&self) -> bool { matches!(self.status, BulkItemStatus::Ok) }
+    pub fn is_failed(&self) -> bool { matches!(self.status, BulkItemStatus::Failed) }
+}
+
+impl BulkSummaryDto {
+    pub fn new(mode: BulkMode, results: Vec<BulkItemResultDto>) -> Self {
+        let succeeded = results.iter().filter(|r| r.is_ok()).count();
+        let failed = results.iter().filter(|r| r.is_failed()).count();
+
+        BulkSummaryDto {
+            mode,
+            applied: failed == 0 || mode == BulkMode::BestEffort,
+            succeeded,
+            failed,
+            results
+        }
+    }
+}
+
+impl TrashItemDto {
+    pub fn new(entity: &TrashItemEntity) -> Self {
+        TrashItemDto {
+            parent_id: entity.task.root_task_id.map(|id| id.to_string()),
+            deleted_at: entity.deleted_at,
+            deleted_by: entity.deleted_by.clone(),
+            subtask_count: entity.subtask_count,
+            base: TaskBaseDto::new(&entity.task)
+        }
+    }
+}
+
+impl RestoreSummaryDto {
+    pub fn new(restored: &[uuid::Uuid], root_id: Option<uuid::Uuid>, reattached: &[uuid::Uuid]) -> Self {
+        RestoreSummaryDto {
+            restored: restored.iter().map(|id| id.to_string()).collect(),
+            root_id: root_id.map(|id| id.to_string()),
+            reattached: reattached.iter().map(|id| id.to_string()).collect()
+        }
+    }
+}
+
+impl TemplateDto {
+    pub fn new(entity: &TemplateEntity, variables: Vec<String>, root: TemplateNodeDto) -> Self {
+        TemplateDto {
+            id: entity.id.to_string(),
+            name: entity.name.clone(),
+            create_date: entity.create_date,
+            variables,
+            root
+        }
+    }
+}
+
+impl TemplateSummaryDto {
+    pub fn new(entity: &TemplateEntity) -> Self {
+        TemplateSummaryDto {
+            id: entity.id.to_string(),
+            name: entity.name.clone(),
+            create_date: entity.create_date
+        }
+    }
+}
+
 impl TaskSearchDto {
     pub fn new(entity: &TaskSearchEntity) -> Self {
         TaskSearchDto { 
@@ -0,0 +1,144 @@
+use std::{sync::Arc, collections::HashMap};
+
+use domain::{models::TaskEntity, enums::StatusCategory};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{dtos::{DueDateReportDto, DueDateNodeDto, DueDateConflictDto, DueDateChangeDto, TaskAction}, repos::TaskRepository, errors::Error, logs::LogService};
+
+pub struct DueDateService {
+    repo: Arc<dyn TaskRepository>,
+    log_service: Arc<LogService>
+}
+
+impl DueDateService {
+    pub fn new(repo: Arc<dyn TaskRepository>, log_service: Arc<LogService>) -> DueDateService {
+        DueDateService { repo, log_service }
+    }
+
+    pub async fn analyze(&self, workspace_id: Uuid, root_id: Uuid) -> Result<DueDateReportDto, Error> {
+        let root = self.repo.get_by_id(workspace_id, root_id).await?;
+        let subtasks = self.repo.get_subtasks_recursive(workspace_id, root_id).await;
+
+        Ok(Subtree::new(&root, &subtasks).report(Utc::now()))
+    }
+
+    // Moves every unfinished task at least to the latest due date found below it, so no child outlives its parent
+    pub async fn propagate(&self, workspace_id: Uuid, root_id: Uuid) -> Result<Vec<DueDateChangeDto>, Error> {
+        let root = self.repo.get_by_id(workspace_id, root_id).await?;
+        let subtasks = self.repo.get_subtasks_recursive(workspace_id, root_id).await;
+        let subtree = Subtree::new(&root, &subtasks);
+
+        let changes = subtree.proposed_changes();
+        let due_dates: Vec<(Uuid, DateTime<Utc>)> = changes.iter().map(|(e, d)| (e.id, *d)).collect();
+        self.repo.update_due_dates(workspace_id, &due_dates).await?;
+
+        for (entity, due_date) in &changes {
+            let payload = format!("{} -> {}", entity.due_date.to_rfc3339(), due_date.to_rfc3339());
+            self.log_service.log_task_action(TaskAction::DueDateAdjusted, Some(entity.id), Some("TaskEntity"), Some(&payload)).await;
+        }
+
+        Ok(changes.iter().map(|(e, d)| DueDateChangeDto::new(e, *d)).collect())
+    }
+}
+
+// Root and its flat subtasks linked back into a tree
+struct Subtree<'a> {
+    root: &'a TaskEntity,
+    nodes: HashMap<Uuid, &'a TaskEntity>,
+    children: HashMap<Uuid, Vec<&'a TaskEntity>>,
+    finish: HashMap<Uuid, DateTime<Utc>> // latest due date of a task and everything below it
+}
+
+impl<'a> Subtree<'a> {
+    fn new(root: &'a TaskEntity, subtasks: &'a [TaskEntity]) -> Subtree<'a> {
+        let mut nodes = HashMap::from([(root.id, root)]);
+        let mut children: HashMap<Uuid, Vec<&TaskEntity>> = HashMap::new();
+
+        for task in subtasks {
+            nodes.insert(task.id, task);
+            if let Some(parent_id) = task.root_task_id {
+                children.entry(parent_id).or_default().push(task);
+            }
+        }
+
+        // Breadth-first order puts every parent before its children, so walking it backwards settles children first
+        let mut order = vec![root];
+        let mut i = 0;
+        while i < order.len() {
+            if let Some(c) = children.get(&order[i].id) {
+                order.extend(c.iter().copied());
+            }
+            i += 1;
+        }
+
+        let mut finish: HashMap<Uuid, DateTime<Utc>> = HashMap::new();
+        for task in order.iter().rev() {
+            let latest_child = children.get(&task.id)
+                .and_then(|c| c.iter().map(|child| finish[&child.id]).max());
+
+            finish.insert(task.id, latest_child.map_or(task.due_date, |d| d.max(task.due_date)));
+        }
+
+        Subtree { root, nodes, children, finish }
+    }
+
+    fn report(&self, now: DateTime<Utc>) -> DueDateReportDto {
+        let mut conflicts = vec![];
+        let mut overdue = vec![];
+
+        for task in self.nodes.values() {
+            // Parent of the root is outside of the subtree and isn't looked at
+            if let Some(parent) = task.root_task_id.and_then(|id| self.nodes.get(&id)) {
+                if task.due_date > parent.due_date {
+                    conflicts.push((task.due_date, DueDateConflictDto::new(parent, task)));
+                }
+            }
+
+            if task.due_date < now && !is_done(task) {
+                overdue.push((task.due_date, DueDateNodeDto::new(task)));
+            }
+        }
+
+        conflicts.sort_by_key(|(due_date, _)| *due_date);
+        overdue.sort_by_key(|(due_date, _)| *due_date);
+
+        let changes = self.proposed_changes().iter().map(|(e, d)| DueDateChangeDto::new(e, *d)).collect();
+
+        DueDateReportDto::new(self.root, conflicts.into_iter().map(|(_, c)| c).collect(), overdue.into_iter().map(|(_, n)| n).collect(), self.latest_chain(), changes)
+    }
+
+    // Follows the child finishing last until a task is itself the latest one of its branch
+    fn latest_chain(&self) -> Vec<DueDateNodeDto> {
+        let mut chain = vec![DueDateNodeDto::new(self.root)];
+        let mut current = self.root;
+
+        while current.due_date < self.finish[&current.id] {
+            let next = self.children[&current.id]
+                .iter()
+                .max_by_key(|c| self.finish[&c.id])
+                .unwrap();
+
+            chain.push(DueDateNodeDto::new(next));
+            current = next;
+        }
+
+        chain
+    }
+
+    // Finished tasks are left as they are
+    fn proposed_changes(&self) -> Vec<(&'a TaskEntity, DateTime<Utc>)> {
+        let mut changes: Vec<(&TaskEntity, DateTime<Utc>)> = self.nodes.values()
+            .filter(|t| !is_done(t) && self.finish[&t.id] > t.due_date)
+            .map(|t| (*t, self.finish[&t.id]))
+            .collect();
+
+        changes.sort_by_key(|(t, _)| t.due_date);
+        changes
+    }
+}
+
+fn is_done(task: &TaskEntity) -> bool {
+    task.status.category == StatusCategory::Done
+}
@@ -4,7 +4,14 @@ use uuid::Uuid;
 pub enum Error {
     EntityNotFound(String),
     InvalidRootBinding(String),
+    InvalidInput(String),
+    TooLarge(String),
+    Conflict(String),
+    InvalidTransition(String),
+    PreconditionFailed(String), // the task changed since the version the client has seen
+    PreconditionRequired(String),
     DbError(String),
+    IoError(String),
 }
 
 impl Error {
@@ -15,4 +22,16 @@ impl Error {
     pub fn invalid_root_binding(message: &str) -> Self {
         Error::InvalidRootBinding(message.to_string())
     }
+
+    pub fn invalid_input(message: &str) -> Self {
+        Error::InvalidInput(message.to_string())
+    }
+
+    pub fn conflict(message: &str) -> Self {
+        Error::Conflict(message.to_string())
+    }
+
+    pub fn precondition_failed(id: Uuid) -> Self {
+        Error::PreconditionFailed(format!("Task {} has been changed in the meantime", id))
+    }
 }
\ No newline at end of file
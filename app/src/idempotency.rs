@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Sha256, Digest};
+use uuid::Uuid;
+
+use crate::{repos::IdempotencyRepository, errors::Error};
+
+const MAX_KEY_LENGTH: usize = 255;
+
+// What to do with a request carrying an `Idempotency-Key`
+pub enum IdempotencyCheck {
+    New, // first time seen, handle it and `complete` the key afterwards
+    Replay { status_code: u16, content_type: Option<String>, body: Vec<u8> },
+    Mismatch, // the key was used for a different request
+    InProgress,
+}
+
+pub struct IdempotencyService {
+    repo: Arc<dyn IdempotencyRepository>,
+    ttl_secs: i64 // how long a key and its response are kept
+}
+
+impl IdempotencyService {
+    pub fn new(repo: Arc<dyn IdempotencyRepository>, ttl_secs: i64) -> IdempotencyService {
+        IdempotencyService { repo, ttl_secs }
+    }
+
+    // Two requests are the same when they have the same method, path with query and body
+    pub fn fingerprint(method: &str, path: &str, body: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(method.as_bytes());
+        hasher.update(b" ");
+        hasher.update(path.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(body);
+
+        hex::encode(hasher.finalize())
+    }
+
+    // Reserves the key for this request, unless it has been seen before in the same workspace
+    pub async fn begin(&self, workspace_id: Uuid, key: &str, fingerprint: &str, now: DateTime<Utc>) -> Result<IdempotencyCheck, Error> {
+        if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+            return Err(Error::InvalidInput(format!("Idempotency key must have 1 to {} characters", MAX_KEY_LENGTH)));
+        }
+
+        let expires_at = Duration::try_seconds(self.ttl_secs.max(1))
+            .and_then(|d| now.checked_add_signed(d))
+            .ok_or(Error::invalid_input("Idempotency key lifetime is out of range"))?;
+
+        if self.repo.reserve(workspace_id, key, fingerprint, now, expires_at).await? {
+            return Ok(IdempotencyCheck::New);
+        }
+
+        // Gone between the two calls means it has just been released, the client can simply retry
+        let Some(entity) = self.repo.get_by_key(workspace_id, key).await else {
+            return Ok(IdempotencyCheck::InProgress);
+        };
+
+        if entity.fingerprint != fingerprint {
+            return Ok(IdempotencyCheck::Mismatch);
+        }
+
+        match entity.status_code {
+            Some(status_code) => Ok(IdempotencyCheck::Replay {
+                status_code: status_code as u16,
+                content_type: entity.content_type,
+                body: entity.body.unwrap_or_default()
+            }),
+            None => Ok(IdempotencyCheck::InProgress)
+        }
+    }
+
+    pub async fn complete(&self, workspace_id: Uuid, key: &str, status_code: u16, content_type: Option<&str>, body: &[u8]) -> Result<(), Error> {
+        self.repo.complete(workspace_id, key, status_code as i16, content_type, body).await
+    }
+
+    // Forgets the key, so a retry after a failure is handled again instead of replaying the failure
+    pub async fn release(&self, workspace_id: Uuid, key: &str) -> Result<(), Error> {
+        self.repo.delete(workspace_id, key).await
+    }
+
+    // One pass of the cleanup worker, returns the number of removed keys
+    pub async fn purge_expired(&self, now: DateTime<Utc>) -> u64 {
+        self.repo.delete_expired(now).await
+    }
+}
@@ -2,4 +2,20 @@ pub mod dtos;
 pub mod repos;
 pub mod tasks;
 pub mod errors;
-pub mod logs;
\ No newline at end of file
+pub mod logs;
+pub mod workspaces;
+pub mod tags;
+pub mod comments;
+pub mod attachments;
+pub mod checklists;pub mod due_dates;
+pub mod recurrence;
+pub mod reminders;
+pub mod notifications;
+pub mod workflow;
+pub mod statuses;
+pub mod trees;
+pub mod templates;
+pub mod trash;
+pub mod archive;
+pub mod ranks;
+pub mod idempotency;
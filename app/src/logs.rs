@@ -19,9 +19,9 @@ impl LogService {
         let log_entry = LogEntity {
             id: Uuid::new_v4(),
             action: action.as_model(),
-            entity_type: entity_type.map_or(None, |s| Some(s.to_string())),
-            entity_id: entity_id,
-            payload: payload.map_or(None, |s| Some(s.to_string())),
+            entity_type: entity_type.map(|s| s.to_string()),
+            entity_id,
+            payload: payload.map(|s| s.to_string()),
             timestamp: Utc::now().timestamp()
         };
 
@@ -32,13 +32,13 @@ impl LogService {
         let (entities, ct) = self.repo
             .get_batch_by_entity_type("TaskEntity", continuation_token, take, descending).await;
 
-        (entities.iter().map(|e| LogEntryDto::new(e)).collect(), ct)
+        (entities.iter().map(LogEntryDto::new).collect(), ct)
     }
 
     pub async fn get_task_action_log_batch_by_task(&self, task_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> (Vec<LogEntryDto>, String) {
         let (entities, ct) = self.repo
             .get_batch_by_entity(task_id, continuation_token, take, descending).await;
 
-        (entities.iter().map(|e| LogEntryDto::new(e)).collect(), ct)
+        (entities.iter().map(LogEntryDto::new).collect(), ct)
     }
 }
\ No newline at end of file
@@ -0,0 +1,250 @@
+use std::{sync::Arc, collections::BTreeMap};
+
+use domain::models::{TaskEntity, NotificationPreferenceEntity, OutboxMessageEntity, StatusEntity};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use uuid::Uuid;
+
+use crate::{dtos::{NotificationPreferencesDto, ReminderDto}, repos::{NotificationRepository, Mailer, ReminderChannel, TaskRepository}, errors::Error};
+
+pub struct NotificationService {
+    repo: Arc<dyn NotificationRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    mailer: Arc<dyn Mailer>,
+    max_attempts: i32,
+    digest_hour: u32 // UTC hour the daily digest goes out at
+}
+
+enum Event {
+    Assignment,
+    StatusChange,
+    Comment,
+    DueSoon,
+}
+
+impl NotificationService {
+    pub fn new(repo: Arc<dyn NotificationRepository>, task_repo: Arc<dyn TaskRepository>, mailer: Arc<dyn Mailer>, max_attempts: i32, digest_hour: u32) -> NotificationService {
+        NotificationService { repo, task_repo, mailer, max_attempts, digest_hour: digest_hour.min(23) }
+    }
+
+    // Everything is enabled for people who never changed their preferences
+    pub async fn get_preferences(&self, email: &str) -> NotificationPreferencesDto {
+        let entity = self.repo.get_preferences(email).await.unwrap_or_else(|| default_preferences(email));
+
+        NotificationPreferencesDto::new(&entity)
+    }
+
+    pub async fn update_preferences(&self, email: &str, details: &NotificationPreferencesDto) -> Result<(), Error> {
+        validate_email(email)?;
+
+        let entity = NotificationPreferenceEntity {
+            email: email.to_lowercase(),
+            assignment: details.assignment,
+            status_change: details.status_change,
+            comments: details.comments,
+            due_soon: details.due_soon,
+            digest: details.digest
+        };
+
+        self.repo.upsert_preferences(entity).await
+    }
+
+    pub async fn notify_assigned(&self, task: &TaskEntity) -> Result<(), Error> {
+        let message = Template::new(format!("Assigned to you: {}", task.summary))
+            .line(format!("You were assigned to \"{}\".", task.summary))
+            .line(format!("Due: {}", task.due_date.to_rfc2822()));
+
+        self.enqueue(task, Event::Assignment, message).await
+    }
+
+    pub async fn notify_status_changed(&self, task: &TaskEntity, from: &StatusEntity) -> Result<(), Error> {
+        let message = Template::new(format!("Status changed: {}", task.summary))
+            .line(format!("\"{}\" moved from {} to {}.", task.summary, from.name, task.status.name));
+
+        self.enqueue(task, Event::StatusChange, message).await
+    }
+
+    pub async fn notify_comment(&self, task: &TaskEntity, author: Option<&str>, body: &str) -> Result<(), Error> {
+        let message = Template::new(format!("New comment: {}", task.summary))
+            .line(format!("{} commented on \"{}\":", author.unwrap_or("Someone"), task.summary))
+            .quote(body.to_string());
+
+        self.enqueue(task, Event::Comment, message).await
+    }
+
+    pub async fn notify_due(&self, task: &TaskEntity, overdue: bool) -> Result<(), Error> {
+        let subject = if overdue { format!("Overdue: {}", task.summary) } else { format!("Due soon: {}", task.summary) };
+        let message = Template::new(subject)
+            .line(format!("\"{}\" is due {}.", task.summary, task.due_date.to_rfc2822()));
+
+        self.enqueue(task, Event::DueSoon, message).await
+    }
+
+    // One pass of the outbox worker, failed messages are retried with exponential backoff
+    pub async fn process_outbox(&self, now: DateTime<Utc>) -> usize {
+        let mut sent = 0;
+
+        for message in self.repo.get_pending(now, self.max_attempts, 50).await {
+            match self.mailer.send(&message.recipient, &message.subject, &message.text_body, Some(&message.html_body)).await {
+                Ok(()) => {
+                    let _ = self.repo.mark_sent(message.id, now).await;
+                    sent += 1;
+                },
+                Err(err) => {
+                    let backoff = Duration::minutes(1 << message.attempts.clamp(0, 10));
+                    let _ = self.repo.mark_failed(message.id, now + backoff, &format!("{:?}", err)).await;
+                }
+            }
+        }
+
+        sent
+    }
+
+    // Folds everything collected before the latest digest hour into one message per recipient.
+    // Later items wait for the next day, so running this often is harmless
+    pub async fn send_digests(&self, now: DateTime<Utc>) -> usize {
+        let today_cutoff = now.with_hour(self.digest_hour).and_then(|d| d.with_minute(0)).and_then(|d| d.with_second(0)).and_then(|d| d.with_nanosecond(0)).unwrap_or(now);
+        let cutoff = if today_cutoff > now { today_cutoff - Duration::days(1) } else { today_cutoff };
+
+        let mut by_recipient: BTreeMap<String, Vec<OutboxMessageEntity>> = BTreeMap::new();
+        for item in self.repo.get_digest_items(cutoff).await {
+            by_recipient.entry(item.recipient.clone()).or_default().push(item);
+        }
+
+        let mut count = 0;
+        for (recipient, items) in by_recipient {
+            let text_body = items.iter().map(|i| format!("{}\n\n{}", i.subject, i.text_body)).collect::<Vec<String>>().join("\n----\n\n");
+            let html_body = items.iter().map(|i| format!("<h3>{}</h3>\n{}", escape(&i.subject), i.html_body)).collect::<Vec<String>>().join("\n<hr>\n");
+            let digest = new_message(&recipient, &format!("Daily digest: {} notification(s)", items.len()), &text_body, &html_body, false, now);
+
+            if self.repo.enqueue(digest).await.is_err() {
+                continue;
+            }
+
+            for item in items {
+                let _ = self.repo.mark_sent(item.id, now).await;
+            }
+
+            count += 1;
+        }
+
+        count
+    }
+
+    // Notifications go to the assignee of the task, provided they want this kind of event
+    async fn enqueue(&self, task: &TaskEntity, event: Event, message: Template) -> Result<(), Error> {
+        let recipient = match &task.assignee {
+            Some(a) => a.to_lowercase(),
+            None => return Ok(())
+        };
+
+        let preferences = self.repo.get_preferences(&recipient).await.unwrap_or_else(|| default_preferences(&recipient));
+        let wanted = match event {
+            Event::Assignment => preferences.assignment,
+            Event::StatusChange => preferences.status_change,
+            Event::Comment => preferences.comments,
+            Event::DueSoon => preferences.due_soon
+        };
+
+        if !wanted {
+            return Ok(());
+        }
+
+        let (text_body, html_body) = message.render();
+        self.repo.enqueue(new_message(&recipient, &message.subject, &text_body, &html_body, preferences.digest, Utc::now())).await
+    }
+}
+
+// Lets the reminder scheduler deliver due-soon and overdue notices by email
+#[async_trait]
+impl ReminderChannel for NotificationService {
+    fn name(&self) -> &str {
+        "notify"
+    }
+
+    async fn deliver(&self, reminder: &ReminderDto) -> Result<(), Error> {
+        let workspace_id = Uuid::parse_str(&reminder.workspace_id).map_err(|_| Error::invalid_input("Invalid workspace id"))?;
+        let task_id = Uuid::parse_str(&reminder.task_id).map_err(|_| Error::invalid_input("Invalid task id"))?;
+        let task = self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        self.notify_due(&task, reminder.kind == "overdue").await
+    }
+}
+
+// Plain text and html renderings of the same short message
+struct Template {
+    subject: String,
+    blocks: Vec<(bool, String)> // quoted or not, text
+}
+
+impl Template {
+    fn new(subject: String) -> Template {
+        Template { subject, blocks: vec![] }
+    }
+
+    fn line(mut self, text: String) -> Template {
+        self.blocks.push((false, text));
+        self
+    }
+
+    fn quote(mut self, text: String) -> Template {
+        self.blocks.push((true, text));
+        self
+    }
+
+    fn render(&self) -> (String, String) {
+        let mut text = String::new();
+        let mut html = String::new();
+
+        for (quoted, block) in &self.blocks {
+            if *quoted {
+                text.push_str(&block.lines().map(|l| format!("> {}\n", l)).collect::<String>());
+                html.push_str(&format!("<blockquote>{}</blockquote>\n", escape(block).replace('\n', "<br>")));
+            } else {
+                text.push_str(&format!("{}\n", block));
+                html.push_str(&format!("<p>{}</p>\n", escape(block)));
+            }
+        }
+
+        (text, html)
+    }
+}
+
+fn new_message(recipient: &str, subject: &str, text_body: &str, html_body: &str, digest: bool, now: DateTime<Utc>) -> OutboxMessageEntity {
+    OutboxMessageEntity {
+        id: Uuid::new_v4(),
+        recipient: recipient.to_string(),
+        subject: subject.to_string(),
+        text_body: text_body.to_string(),
+        html_body: html_body.to_string(),
+        digest,
+        create_date: now,
+        attempts: 0,
+        next_attempt_date: now,
+        sent_date: None,
+        last_error: None
+    }
+}
+
+fn default_preferences(email: &str) -> NotificationPreferenceEntity {
+    NotificationPreferenceEntity {
+        email: email.to_string(),
+        assignment: true,
+        status_change: true,
+        comments: true,
+        due_soon: true,
+        digest: false
+    }
+}
+
+pub fn validate_email(email: &str) -> Result<(), Error> {
+    match email.split_once('@') {
+        Some((user, domain)) if !user.is_empty() && !domain.is_empty() && !email.contains(char::is_whitespace) => Ok(()),
+        _ => Err(Error::invalid_input("Invalid email address"))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,186 @@
+// Lexicographic ranks for the manual ordering of sibling tasks. A rank is a base 36 fraction written without the leading "0.",
+// so there is always room for another one between two neighbours and a move rewrites a single row. Ranks are compared byte by byte
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+// Ranks at either end of a list are counted up or down in the last of `WIDTH` digits, starting from the middle.
+// That leaves room for about a billion tasks added at each end before ranks get any longer
+const WIDTH: usize = 6;
+const FIRST: &str = "i00000";
+
+fn digit_at(rank: &[u8], i: usize) -> usize {
+    rank.get(i).and_then(|c| DIGITS.iter().position(|d| d == c)).unwrap_or(0)
+}
+
+// A rank strictly between `lower` and `upper`, a missing bound stands for the start or the end of the list
+pub fn between(lower: Option<&str>, upper: Option<&str>) -> String {
+    match (lower, upper) {
+        (None, None) => FIRST.to_string(),
+        (Some(lower), None) => step(lower.as_bytes(), true).unwrap_or_else(|| above(lower.as_bytes(), 0, String::new())),
+        (None, Some(upper)) => step(upper.as_bytes(), false).unwrap_or_else(|| midpoint(b"", upper.as_bytes())),
+        (Some(lower), Some(upper)) => midpoint(lower.as_bytes(), upper.as_bytes())
+    }
+}
+
+// The first `WIDTH` digits of the rank moved one step up or down, none when that runs out of digits
+fn step(rank: &[u8], up: bool) -> Option<String> {
+    let mut digits: Vec<usize> = (0..WIDTH).map(|i| digit_at(rank, i)).collect();
+
+    let mut i = WIDTH;
+    loop {
+        if i == 0 {
+            return None;
+        }
+        i -= 1;
+
+        if up && digits[i] < DIGITS.len() - 1 {
+            digits[i] += 1;
+            break;
+        }
+        if !up && digits[i] > 0 {
+            digits[i] -= 1;
+            break;
+        }
+
+        digits[i] = if up { 0 } else { DIGITS.len() - 1 };
+    }
+
+    // All zeros is the very start of the space, nothing could ever go before it
+    if digits.iter().all(|d| *d == 0) {
+        return None;
+    }
+
+    Some(digits.into_iter().map(|d| DIGITS[d] as char).collect())
+}
+
+// Bounds in the wrong order (ties left by moved or restored tasks) fall back to a rank right after `lower`
+fn midpoint(lower: &[u8], upper: &[u8]) -> String {
+    if upper <= lower {
+        return above(lower, 0, String::new());
+    }
+
+    let mut rank = String::new();
+    for i in 0..lower.len().max(upper.len()) {
+        let (lo, hi) = (digit_at(lower, i), digit_at(upper, i));
+
+        if lo == hi {
+            rank.push(DIGITS[lo] as char);
+            continue;
+        }
+
+        if lo > hi {
+            break;
+        }
+
+        if hi - lo > 1 {
+            rank.push(DIGITS[(lo + hi) / 2] as char);
+            return rank;
+        }
+
+        // Adjacent digits, keep the lower one and go past the rest of `lower`
+        rank.push(DIGITS[lo] as char);
+        return above(lower, i + 1, rank);
+    }
+
+    // Only bounds that differ by trailing zeros get here, nothing fits in between
+    above(lower, 0, String::new())
+}
+
+// Extends `rank`, a copy of `lower` up to `start`, into something greater than `lower`
+fn above(lower: &[u8], start: usize, mut rank: String) -> String {
+    let mut i = start;
+    loop {
+        let lo = digit_at(lower, i);
+        if lo < DIGITS.len() - 1 {
+            rank.push(DIGITS[(lo + DIGITS.len()) / 2] as char);
+            return rank;
+        }
+
+        rank.push(DIGITS[lo] as char);
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_rank_is_in_the_middle() {
+        assert_eq!(between(None, None), FIRST);
+    }
+
+    #[test]
+    fn fits_between_adjacent_ranks() {
+        let next = between(Some(FIRST), None);
+        assert_eq!(next, "i00001");
+
+        let middle = between(Some(FIRST), Some(&next));
+        assert!(FIRST < middle.as_str() && middle < next);
+    }
+
+    #[test]
+    fn keeps_fitting_between_neighbours() {
+        let lower = FIRST.to_string();
+        let mut upper = between(Some(&lower), None);
+
+        for _ in 0..200 {
+            let rank = between(Some(&lower), Some(&upper));
+            assert!(lower < rank && rank < upper, "{} {} {}", lower, rank, upper);
+            upper = rank;
+        }
+    }
+
+    #[test]
+    fn repeated_inserts_at_the_head() {
+        let mut head = between(None, None);
+
+        for _ in 0..1000 {
+            let rank = between(None, Some(&head));
+            assert!(rank < head, "{} {}", rank, head);
+            head = rank;
+        }
+
+        assert_eq!(head.len(), WIDTH);
+    }
+
+    #[test]
+    fn repeated_inserts_at_the_tail() {
+        let mut tail = between(None, None);
+
+        for _ in 0..1000 {
+            let rank = between(Some(&tail), None);
+            assert!(rank > tail, "{} {}", rank, tail);
+            tail = rank;
+        }
+
+        assert_eq!(tail.len(), WIDTH);
+    }
+
+    #[test]
+    fn head_goes_on_past_the_start_of_the_space() {
+        let mut head = "000001".to_string();
+
+        for _ in 0..100 {
+            let rank = between(None, Some(&head));
+            assert!(rank.as_str() > "" && rank < head, "{} {}", rank, head);
+            head = rank;
+        }
+    }
+
+    #[test]
+    fn tail_goes_on_past_the_end_of_the_space() {
+        let mut tail = "zzzzzz".to_string();
+
+        for _ in 0..100 {
+            let rank = between(Some(&tail), None);
+            assert!(rank > tail, "{} {}", rank, tail);
+            tail = rank;
+        }
+    }
+
+    #[test]
+    fn bounds_in_the_wrong_order_give_a_rank_after_the_lower_one() {
+        let rank = between(Some("i00005"), Some("i00001"));
+        assert!(rank.as_str() > "i00005");
+    }
+}
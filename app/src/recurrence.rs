@@ -0,0 +1,408 @@
+use std::{sync::Arc, collections::HashMap};
+
+use domain::models::{TaskEntity, RecurrenceEntity, StatusEntity};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+use uuid::Uuid;
+
+use crate::{dtos::{UpsertRecurrenceDto, TaskAction}, repos::{RecurrenceRepository, TaskRepository}, errors::Error, logs::LogService, statuses::StatusService, ranks};
+
+pub struct RecurrenceService {
+    repo: Arc<dyn RecurrenceRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    status_service: Arc<StatusService>,
+    log_service: Arc<LogService>
+}
+
+impl RecurrenceService {
+    pub fn new(repo: Arc<dyn RecurrenceRepository>, task_repo: Arc<dyn TaskRepository>, status_service: Arc<StatusService>, log_service: Arc<LogService>) -> RecurrenceService {
+        RecurrenceService { repo, task_repo, status_service, log_service }
+    }
+
+    pub async fn get_by_task(&self, task_id: Uuid) -> Option<RecurrenceEntity> {
+        self.repo.get_by_task(task_id).await
+    }
+
+    // Changing the rule keeps the position of the task within its series
+    pub async fn set_recurrence(&self, workspace_id: Uuid, task_id: Uuid, details: &UpsertRecurrenceDto) -> Result<(), Error> {
+        let rule = RecurrenceRule::parse(&details.rule)?;
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        let entity = RecurrenceEntity {
+            task_id,
+            rule: rule.to_string(),
+            clone_subtasks: details.clone_subtasks,
+            occurrence: 1,
+            previous_task_id: None,
+            next_task_id: None
+        };
+
+        self.repo.upsert(entity).await?;
+        self.log_service.log_task_action(TaskAction::RecurrenceChanged, Some(task_id), Some("TaskEntity"), Some(&rule.to_string())).await;
+
+        Ok(())
+    }
+
+    pub async fn remove_recurrence(&self, workspace_id: Uuid, task_id: Uuid) -> Result<(), Error> {
+        self.task_repo.get_by_id(workspace_id, task_id).await?;
+
+        self.repo.delete(task_id).await?;
+        self.log_service.log_task_action(TaskAction::RecurrenceChanged, Some(task_id), Some("TaskEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Called once the task is done. Every occurrence produces at most one successor, so reopening and finishing it again is harmless
+    pub async fn generate_next(&self, task: &TaskEntity) -> Result<Option<Uuid>, Error> {
+        let recurrence = match self.repo.get_by_task(task.id).await {
+            Some(r) if r.next_task_id.is_none() => r,
+            _ => return Ok(None)
+        };
+
+        let next_due_date = match RecurrenceRule::parse(&recurrence.rule)?.next(task.due_date, recurrence.occurrence) {
+            Some(d) => d,
+            None => return Ok(None)
+        };
+
+        // The next occurrence goes right after the current one, copied subtasks keep their order
+        let status = self.status_service.initial_status(task.workspace_id).await?;
+        let next_id = Uuid::new_v4();
+        let next_sibling_rank = self.task_repo.get_adjacent_rank(task.workspace_id, task.root_task_id, &task.rank, true, task.id).await;
+        let mut copies = vec![copy_task(task, next_id, task.root_task_id, next_due_date, ranks::between(Some(&task.rank), next_sibling_rank.as_deref()), &status)];
+        let mut created = vec![(next_id, task.id)];
+
+        if recurrence.clone_subtasks {
+            let shift = next_due_date - task.due_date;
+            let subtasks = self.task_repo.get_subtasks_recursive(task.workspace_id, task.id).await;
+            let mut children: HashMap<Uuid, Vec<&TaskEntity>> = HashMap::new();
+            for subtask in &subtasks {
+                if let Some(parent_id) = subtask.root_task_id {
+                    children.entry(parent_id).or_default().push(subtask);
+                }
+            }
+
+            // Parents go first, their copies have to exist before the children point at them
+            let mut pending = vec![(task.id, next_id)];
+            while let Some((original_id, copy_id)) = pending.pop() {
+                for child in children.remove(&original_id).unwrap_or_default() {
+                    let child_copy_id = Uuid::new_v4();
+                    let due_date = child.due_date.checked_add_signed(shift).ok_or(Error::invalid_input("Due date shift is out of range"))?;
+                    copies.push(copy_task(child, child_copy_id, Some(copy_id), due_date, child.rank.clone(), &status));
+                    created.push((child_copy_id, child.id));
+                    pending.push((child.id, child_copy_id));
+                }
+            }
+        }
+
+        // The whole copy is written at once, so a failure halfway leaves no partial tree behind
+        self.task_repo.insert_many(copies).await?;
+
+        for (id, source_id) in created {
+            self.log_service.log_task_action(TaskAction::Create, Some(id), Some("TaskEntity"), Some(&source_id.to_string())).await;
+        }
+
+        let next = RecurrenceEntity {
+            task_id: next_id,
+            rule: recurrence.rule,
+            clone_subtasks: recurrence.clone_subtasks,
+            occurrence: recurrence.occurrence + 1,
+            previous_task_id: Some(task.id),
+            next_task_id: None
+        };
+
+        self.repo.upsert(next).await?;
+        self.repo.set_next(task.id, next_id).await?;
+        self.log_service.log_task_action(TaskAction::RecurrenceGenerated, Some(task.id), Some("TaskEntity"), Some(&next_id.to_string())).await;
+
+        Ok(Some(next_id))
+    }
+
+}
+
+// Keeps the date arithmetic of `next` in range
+const MAX_INTERVAL: u32 = 1000;
+
+fn copy_task(source: &TaskEntity, id: Uuid, root_task_id: Option<Uuid>, due_date: DateTime<Utc>, rank: String, status: &StatusEntity) -> TaskEntity {
+    TaskEntity {
+        id,
+        workspace_id: source.workspace_id,
+        root_task_id,
+        summary: source.summary.clone(),
+        description: source.description.clone(),
+        create_date: Utc::now(),
+        due_date,
+        assignee: source.assignee.clone(),
+        resolution: None,
+        auto_complete: source.auto_complete,
+        archived_at: None,
+        rank,
+        version: 1,
+        priority: source.priority.clone(),
+        status: status.clone()
+    }
+}
+
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+// Subset of RFC 5545 RRULE: FREQ (DAILY, WEEKLY, MONTHLY), INTERVAL, BYDAY (plain weekdays), UNTIL or COUNT
+pub struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    by_day: Vec<Weekday>,
+    until: Option<DateTime<Utc>>,
+    count: Option<i32>,
+}
+
+impl RecurrenceRule {
+    pub fn parse(rule: &str) -> Result<RecurrenceRule, Error> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = vec![];
+        let mut until = None;
+        let mut count = None;
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| invalid_rule(part))?;
+
+            match key.to_uppercase().as_str() {
+                "FREQ" => frequency = Some(match value.to_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    _ => return Err(invalid_rule(part))
+                }),
+                "INTERVAL" => interval = value.parse::<u32>().ok().filter(|i| (1..=MAX_INTERVAL).contains(i)).ok_or_else(|| invalid_rule(part))?,
+                "BYDAY" => by_day = value.split(',').map(parse_weekday).collect::<Option<Vec<Weekday>>>().ok_or_else(|| invalid_rule(part))?,
+                "UNTIL" => until = Some(parse_until(value).ok_or_else(|| invalid_rule(part))?),
+                "COUNT" => count = Some(value.parse::<i32>().ok().filter(|c| *c > 0).ok_or_else(|| invalid_rule(part))?),
+                _ => return Err(invalid_rule(part))
+            }
+        }
+
+        if until.is_some() && count.is_some() {
+            return Err(Error::invalid_input("Recurrence rule can't have both UNTIL and COUNT"));
+        }
+
+        let frequency = frequency.ok_or_else(|| Error::invalid_input("Recurrence rule must have FREQ"))?;
+
+        Ok(RecurrenceRule { frequency, interval, by_day, until, count })
+    }
+
+    // Due date of the occurrence following the given one, none when the series is over
+    pub fn next(&self, due_date: DateTime<Utc>, occurrence: i32) -> Option<DateTime<Utc>> {
+        if self.count.is_some_and(|c| occurrence >= c) {
+            return None;
+        }
+
+        let interval = self.interval as i64;
+        let next = if self.by_day.is_empty() {
+            match self.frequency {
+                Frequency::Daily => due_date.checked_add_signed(Duration::days(interval)),
+                Frequency::Weekly => due_date.checked_add_signed(Duration::weeks(interval)),
+                // Months without such a day are skipped, as RFC 5545 does
+                Frequency::Monthly => (1..=24).find_map(|k| add_months(due_date, k * self.interval))
+            }
+        } else {
+            // First matching weekday that falls into a period picked by the interval. Every weekday of such a period
+            // comes up within one period past the interval, a rule that never matches gives up there
+            let period_days = match self.frequency {
+                Frequency::Daily | Frequency::Weekly => 7,
+                Frequency::Monthly => 31
+            };
+
+            (1..=(period_days * (interval + 1)))
+                .map_while(|d| due_date.checked_add_signed(Duration::days(d)))
+                .find(|d| self.by_day.contains(&d.weekday()) && self.periods_between(due_date, *d) % interval == 0)
+        };
+
+        next.filter(|d| self.until.is_none_or(|u| *d <= u))
+    }
+
+    fn periods_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+        match self.frequency {
+            Frequency::Daily => (to.date_naive() - from.date_naive()).num_days(),
+            Frequency::Weekly => (week_start(to) - week_start(from)).num_days() / 7,
+            Frequency::Monthly => (to.year() * 12 + to.month() as i32 - from.year() * 12 - from.month() as i32) as i64
+        }
+    }
+}
+
+impl std::fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY"
+        };
+
+        write!(f, "FREQ={};INTERVAL={}", frequency, self.interval)?;
+
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_rule(part: &str) -> Error {
+    Error::InvalidInput(format!("Unsupported recurrence rule part: {}", part))
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code.trim().to_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU"
+    }
+}
+
+// Both forms from the RFC: a date (the whole day counts) or a UTC date-time
+fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Some(date_time.and_utc());
+    }
+
+    NaiveDate::parse_from_str(value, "%Y%m%d").ok()
+        .and_then(|d| d.and_hms_opt(23, 59, 59))
+        .map(|d| d.and_utc())
+}
+
+fn add_months(date: DateTime<Utc>, months: u32) -> Option<DateTime<Utc>> {
+    let total = date.year() * 12 + date.month0() as i32 + months as i32;
+
+    NaiveDate::from_ymd_opt(total / 12, (total % 12) as u32 + 1, date.day())
+        .map(|d| d.and_time(date.time()).and_utc())
+}
+
+fn week_start(date: DateTime<Utc>) -> NaiveDate {
+    let date = date.date_naive();
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parses_supported_rules() {
+        let cases = [
+            ("FREQ=DAILY", "FREQ=DAILY;INTERVAL=1"),
+            ("RRULE:freq=weekly;interval=2", "FREQ=WEEKLY;INTERVAL=2"),
+            ("FREQ=WEEKLY;BYDAY=MO,we,FR", "FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,WE,FR"),
+            ("FREQ=MONTHLY;COUNT=3", "FREQ=MONTHLY;INTERVAL=1;COUNT=3"),
+            ("FREQ=DAILY;UNTIL=20261231", "FREQ=DAILY;INTERVAL=1;UNTIL=20261231T235959Z"),
+            ("FREQ=DAILY;UNTIL=20261231T120000Z", "FREQ=DAILY;INTERVAL=1;UNTIL=20261231T120000Z"),
+            (" FREQ=DAILY;INTERVAL=1000; ", "FREQ=DAILY;INTERVAL=1000"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(RecurrenceRule::parse(rule).map(|r| r.to_string()).ok().as_deref(), Some(expected), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let cases = [
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;INTERVAL=1001",
+            "FREQ=DAILY;INTERVAL=-1",
+            "FREQ=DAILY;INTERVAL=x",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;UNTIL=2026-12-31",
+            "FREQ=DAILY;COUNT=2;UNTIL=20261231",
+            "FREQ=DAILY;BYMONTH=1",
+            "FREQ",
+        ];
+
+        for rule in cases {
+            assert!(RecurrenceRule::parse(rule).is_err(), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn calculates_next_due_date() {
+        let cases = [
+            ("FREQ=DAILY", "2026-10-19T09:00:00Z", 1, Some("2026-10-20T09:00:00Z")),
+            ("FREQ=DAILY;INTERVAL=3", "2026-12-30T09:00:00Z", 1, Some("2027-01-02T09:00:00Z")),
+            ("FREQ=WEEKLY;INTERVAL=2", "2026-10-19T09:00:00Z", 1, Some("2026-11-02T09:00:00Z")),
+            // Month ends, months without the day are skipped
+            ("FREQ=MONTHLY", "2026-01-31T09:00:00Z", 1, Some("2026-03-31T09:00:00Z")),
+            ("FREQ=MONTHLY", "2026-03-31T09:00:00Z", 1, Some("2026-05-31T09:00:00Z")),
+            ("FREQ=MONTHLY", "2026-12-15T09:00:00Z", 1, Some("2027-01-15T09:00:00Z")),
+            ("FREQ=MONTHLY;INTERVAL=12", "2028-02-29T09:00:00Z", 1, Some("2032-02-29T09:00:00Z")),
+            // Due dates are UTC, a DST change of any zone doesn't move them
+            ("FREQ=DAILY", "2026-03-28T09:00:00Z", 1, Some("2026-03-29T09:00:00Z")),
+            ("FREQ=WEEKLY", "2026-10-22T09:00:00Z", 1, Some("2026-10-29T09:00:00Z")),
+            // Weekdays, 2026-10-19 is a Monday
+            ("FREQ=WEEKLY;BYDAY=MO,WE", "2026-10-19T09:00:00Z", 1, Some("2026-10-21T09:00:00Z")),
+            ("FREQ=WEEKLY;BYDAY=MO,WE", "2026-10-21T09:00:00Z", 1, Some("2026-10-26T09:00:00Z")),
+            ("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO", "2026-10-19T09:00:00Z", 1, Some("2026-11-02T09:00:00Z")),
+            ("FREQ=MONTHLY;BYDAY=FR", "2026-10-30T09:00:00Z", 1, Some("2026-11-06T09:00:00Z")),
+            ("FREQ=WEEKLY;INTERVAL=1000;BYDAY=MO", "2026-10-19T09:00:00Z", 1, Some("2045-12-18T09:00:00Z")),
+            // Every seventh day from a Monday is never a Tuesday
+            ("FREQ=DAILY;INTERVAL=7;BYDAY=TU", "2026-10-19T09:00:00Z", 1, None),
+            // End of the series
+            ("FREQ=DAILY;COUNT=3", "2026-10-19T09:00:00Z", 2, Some("2026-10-20T09:00:00Z")),
+            ("FREQ=DAILY;COUNT=3", "2026-10-19T09:00:00Z", 3, None),
+            ("FREQ=DAILY;UNTIL=20261020", "2026-10-19T09:00:00Z", 1, Some("2026-10-20T09:00:00Z")),
+            ("FREQ=DAILY;UNTIL=20261020", "2026-10-20T09:00:00Z", 1, None),
+        ];
+
+        for (rule, due_date, occurrence, expected) in cases {
+            let next = RecurrenceRule::parse(rule).unwrap().next(at(due_date), occurrence);
+            assert_eq!(next, expected.map(at), "{} from {}", rule, due_date);
+        }
+    }
+
+    #[test]
+    fn out_of_range_dates_end_the_series() {
+        let last = DateTime::<Utc>::MAX_UTC - Duration::days(30);
+
+        for rule in ["FREQ=DAILY;INTERVAL=1000", "FREQ=WEEKLY;INTERVAL=1000", "FREQ=MONTHLY;INTERVAL=1000", "FREQ=WEEKLY;INTERVAL=1000;BYDAY=MO"] {
+            assert_eq!(RecurrenceRule::parse(rule).unwrap().next(last, 1), None, "{}", rule);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use domain::models::ReminderEntity;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::{dtos::{ReminderDto, TaskAction}, repos::{ReminderRepository, ReminderChannel, TaskRepository}, errors::Error, logs::LogService};
+
+pub struct ReminderService {
+    repo: Arc<dyn ReminderRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    channels: Vec<Arc<dyn ReminderChannel>>,
+    log_service: Arc<LogService>,
+    offsets: Vec<i64> // minutes before the due date, ascending
+}
+
+impl ReminderService {
+    pub fn new(repo: Arc<dyn ReminderRepository>, task_repo: Arc<dyn TaskRepository>, channels: Vec<Arc<dyn ReminderChannel>>, log_service: Arc<LogService>, offsets: &[i64]) -> ReminderService {
+        let mut offsets: Vec<i64> = offsets.iter().copied().filter(|o| *o > 0).collect();
+        offsets.sort();
+        offsets.dedup();
+
+        ReminderService { repo, task_repo, channels, log_service, offsets }
+    }
+
+    pub async fn get_reminders(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<ReminderDto>, Error> {
+        let task = self.task_repo.get_by_id(workspace_id, task_id).await?;
+        let entities = self.repo.get_by_task(task_id).await;
+
+        Ok(entities.iter().map(|e| ReminderDto::new(e, &task)).collect())
+    }
+
+    // One pass of the scheduler. A reminder is stored before delivery, so a crash loses it rather than sends it twice.
+    // Failures go to the task log as `ReminderFailed`
+    pub async fn fire_due(&self, now: DateTime<Utc>) -> usize {
+        let horizon = now + Duration::minutes(self.offsets.last().copied().unwrap_or(0));
+        let mut fired = 0;
+
+        for task in self.repo.get_unfinished_due_before(horizon).await {
+            // Only the closest reminder counts, a scheduler that was down doesn't send the whole backlog
+            let kind = if task.due_date <= now {
+                "overdue".to_string()
+            } else {
+                match self.offsets.iter().find(|o| task.due_date - Duration::minutes(**o) <= now) {
+                    Some(offset) => format!("before:{}", offset),
+                    None => continue
+                }
+            };
+
+            let entity = ReminderEntity {
+                id: Uuid::new_v4(),
+                task_id: task.id,
+                kind,
+                due_date: task.due_date,
+                fire_date: now
+            };
+            let reminder = ReminderDto::new(&entity, &task);
+
+            match self.repo.try_insert(entity).await {
+                Ok(true) => {},
+                Ok(false) => continue,
+                Err(err) => {
+                    let payload = format!("{} wasn't stored: {:?}", reminder.kind, err);
+                    self.log_service.log_task_action(TaskAction::ReminderFailed, Some(task.id), Some("TaskEntity"), Some(&payload)).await;
+                    continue;
+                }
+            }
+
+            for channel in &self.channels {
+                if let Err(err) = channel.deliver(&reminder).await {
+                    let payload = format!("{} wasn't delivered via {}: {:?}", reminder.kind, channel.name(), err);
+                    self.log_service.log_task_action(TaskAction::ReminderFailed, Some(task.id), Some("TaskEntity"), Some(&payload)).await;
+                }
+            }
+
+            fired += 1;
+        }
+
+        fired
+    }
+}
@@ -1,10 +1,40 @@
-use domain::{models::{LogEntity, TaskEntity, TaskSearchEntity}, enums::{TaskPriority, TaskStatus}};
+use domain::{models::{LogEntity, TaskEntity, TaskSearchEntity, WorkspaceEntity, TagEntity, CommentEntity, CommentRevisionEntity, AttachmentEntity, ChecklistItemEntity, RecurrenceEntity, ReminderEntity, NotificationPreferenceEntity, OutboxMessageEntity, WorkflowTransitionEntity, WorkflowHookEntity, StatusEntity, PriorityEntity, TaskTreeNodeEntity, TemplateEntity, TemplateNodeEntity, TrashItemEntity, IdempotencyKeyEntity}, enums::StatusCategory};
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::errors::Error;
+use crate::{errors::Error, dtos::ReminderDto};
+
+// Narrows task listings. Empty tag list means no tag filtering at all
+#[derive(Default)]
+pub struct TaskFilter {
+    pub tag_ids: Vec<Uuid>,
+    pub match_all_tags: bool,
+    pub include_archived: bool,
+}
+
+// Columns written by a task update, the ones left as None are not touched
+#[derive(Default)]
+pub struct TaskChanges {
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub priority_id: Option<Uuid>,
+    pub status_id: Option<Uuid>,
+    pub resolution: Option<Option<String>>,
+    pub expected_version: Option<i32>, // nothing is written unless the row still has this version
+}
+
+// One write of an all-or-nothing bulk request, each of them fails when its task is gone by then
+pub enum TaskWrite {
+    Update { id: Uuid, changes: TaskChanges },
+    Root { id: Uuid, root_task_id: Option<Uuid>, rank: String, expected_version: Option<i32> },
+    Trash { ids: Vec<Uuid>, expected_version: Option<i32> }, // the task first, then its subtasks
+    TrashKeepingSubtasks { id: Uuid, new_root_id: Option<Uuid>, expected_version: Option<i32> },
+    AddTag { id: Uuid, tag_id: Uuid },
+    RemoveTag { id: Uuid, tag_id: Uuid },
+}
 
 #[async_trait]
 pub trait LogRepository : Send + Sync {
@@ -13,15 +43,207 @@ pub trait LogRepository : Send + Sync {
     async fn get_batch_by_entity(&self, entity_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> (Vec<LogEntity>, String);
 }
 
+#[async_trait]
+pub trait WorkspaceRepository : Send + Sync {
+    async fn get_by_id(&self, id: Uuid) -> Result<WorkspaceEntity, Error>;
+    async fn get_all(&self) -> Vec<WorkspaceEntity>;
+    async fn insert(&self, entity: WorkspaceEntity, statuses: Vec<StatusEntity>, priorities: Vec<PriorityEntity>) -> Result<(), Error>; // Together with its default statuses and priorities, all or nothing
+    async fn update(&self, id: Uuid, name: &str) -> Result<(), Error>;
+    async fn delete(&self, id: Uuid) -> Result<(), Error>;
+}
+
+// Every query is scoped by workspace id, so a task from one workspace is invisible from another
 #[async_trait]
 pub trait TaskRepository : Send + Sync {
-    async fn get_by_id(&self, id: Uuid) -> Result<TaskEntity, Error>;
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TaskEntity, Error>;
     async fn insert(&self, entity: TaskEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
-    async fn delete(&self, id: Uuid) -> Result<(), Error>;
-    async fn get_subtasks(&self, task_id: Uuid) -> Vec<TaskEntity>;
-    async fn get_root_task_batch(&self, take: i32, continuation_token: &str, sort_by: &str, descending: bool) -> (Vec<TaskEntity>, String);
-    async fn search_tasks(&self, phrase: &str, take: i32, continuation_token: &str) -> (Vec<TaskSearchEntity>, String);
-    async fn get_all_subtasks_recursive(&self, task_id: Uuid) -> Vec<Uuid>;
-    async fn update_task_root(&self, task_id: Uuid, new_root_id: Option<Uuid>) -> Result<(), Error>;
-    async fn update_task(&self, id: Uuid, summary: &str, description: Option<&str>, due_date: DateTime<Utc>, priority: TaskPriority, status: TaskStatus) -> Result<(), Error>;
-}
\ No newline at end of file
+    async fn insert_many(&self, entities: Vec<TaskEntity>) -> Result<(), Error>; // All or nothing, parents have to come before their subtasks
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error>;
+    async fn trash_many(&self, workspace_id: Uuid, ids: &[Uuid], deleted_at: DateTime<Utc>, deleted_by: Option<&str>, expected_version: Option<i32>) -> Result<(), Error>; // Moves to the trash, already trashed ones are left as they are. The first id is the guarded one
+    async fn trash_keeping_subtasks(&self, workspace_id: Uuid, id: Uuid, new_root_id: Option<Uuid>, deleted_at: DateTime<Utc>, deleted_by: Option<&str>, expected_version: Option<i32>) -> Result<(), Error>; // Direct subtasks move to `new_root_id` first, all or nothing
+    async fn get_subtasks(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<TaskEntity>;
+    async fn get_root_task_batch(&self, workspace_id: Uuid, filter: &TaskFilter, take: i32, continuation_token: &str, sort_by: &str, descending: bool) -> (Vec<TaskEntity>, String);
+    async fn search_tasks(&self, workspace_id: Uuid, filter: &TaskFilter, phrase: &str, take: i32, continuation_token: &str) -> (Vec<TaskSearchEntity>, String);
+    async fn get_all_subtasks_recursive(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<Uuid>;
+    async fn get_subtasks_recursive(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<TaskEntity>; // Same walk as above, but whole entities
+    async fn get_ancestors(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<TaskEntity>; // From the top task down to the direct parent
+    async fn get_subtree(&self, workspace_id: Uuid, task_id: Uuid, max_depth: Option<i32>) -> Vec<TaskTreeNodeEntity>; // The task first, then by depth, empty when it doesn't exist
+    async fn count_subtasks_by_status(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<(StatusEntity, i64)>; // Same walk again, unused statuses are left out
+    async fn update_due_dates(&self, workspace_id: Uuid, due_dates: &[(Uuid, DateTime<Utc>)]) -> Result<(), Error>; // All or nothing
+    async fn update_task_root(&self, workspace_id: Uuid, task_id: Uuid, new_root_id: Option<Uuid>, rank: &str, expected_version: Option<i32>) -> Result<(), Error>; // Moves within the siblings as well
+    async fn get_last_rank(&self, workspace_id: Uuid, root_task_id: Option<Uuid>) -> Option<String>; // Highest rank among the subtasks of `root_task_id`, root tasks when none
+    async fn get_adjacent_rank(&self, workspace_id: Uuid, root_task_id: Option<Uuid>, rank: &str, after: bool, except_id: Uuid) -> Option<String>; // Closest sibling rank after (or before) `rank`
+    async fn update_assignee(&self, workspace_id: Uuid, id: Uuid, assignee: Option<&str>) -> Result<(), Error>;
+    async fn update_resolution(&self, workspace_id: Uuid, id: Uuid, resolution: Option<&str>) -> Result<(), Error>;
+    async fn update_status(&self, workspace_id: Uuid, id: Uuid, status_id: Uuid) -> Result<(), Error>;
+    async fn update_auto_complete(&self, workspace_id: Uuid, id: Uuid, auto_complete: bool) -> Result<(), Error>;
+    async fn update_archived(&self, workspace_id: Uuid, ids: &[Uuid], archived_at: Option<DateTime<Utc>>) -> Result<(), Error>; // None unarchives
+    async fn get_done_trees_before(&self, done_before: DateTime<Utc>) -> Vec<TaskEntity>; // Unarchived root tasks whose whole tree has been done since before the date, across all workspaces
+    #[allow(clippy::too_many_arguments)]
+    async fn update_task(&self, workspace_id: Uuid, id: Uuid, changes: &TaskChanges) -> Result<(), Error>; // Writes only the given columns, bumps the version
+    async fn write_all(&self, workspace_id: Uuid, writes: &[TaskWrite], deleted_at: DateTime<Utc>, deleted_by: Option<&str>) -> Result<(), (usize, Error)>; // One transaction, rolled back on the first failure whose index comes with the error
+}
+
+#[async_trait]
+pub trait StatusRepository : Send + Sync {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<StatusEntity, Error>;
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<StatusEntity>; // Ordered by position
+    async fn insert(&self, entity: StatusEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, color: &str, position: i32, category: StatusCategory) -> Result<(), Error>;
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error>;
+    async fn count_tasks(&self, id: Uuid) -> i64;
+}
+
+#[async_trait]
+pub trait PriorityRepository : Send + Sync {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<PriorityEntity, Error>;
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<PriorityEntity>; // Ordered by position
+    async fn insert(&self, entity: PriorityEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, color: &str, position: i32) -> Result<(), Error>;
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error>;
+    async fn count_tasks(&self, id: Uuid) -> i64;
+}
+
+#[async_trait]
+pub trait TagRepository : Send + Sync {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TagEntity, Error>;
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<TagEntity>;
+    async fn insert(&self, entity: TagEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, color: &str) -> Result<(), Error>;
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error>;
+    async fn add_to_task(&self, task_id: Uuid, tag_id: Uuid) -> Result<(), Error>;
+    async fn remove_from_task(&self, task_id: Uuid, tag_id: Uuid) -> Result<(), Error>;
+    async fn get_by_tasks(&self, task_ids: &[Uuid]) -> Vec<(Uuid, TagEntity)>; // Pairs of task id and tag
+}
+
+#[async_trait]
+pub trait CommentRepository : Send + Sync {
+    async fn get_by_id(&self, task_id: Uuid, id: Uuid) -> Result<CommentEntity, Error>;
+    async fn get_batch_by_task(&self, task_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> (Vec<CommentEntity>, String);
+    async fn insert(&self, entity: CommentEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn update(&self, task_id: Uuid, id: Uuid, body: &str, update_date: DateTime<Utc>) -> Result<(), Error>; // Keeps the previous body as a revision
+    async fn delete(&self, task_id: Uuid, id: Uuid) -> Result<(), Error>;
+    async fn get_revisions(&self, comment_id: Uuid) -> Vec<CommentRevisionEntity>;
+}
+
+#[async_trait]
+pub trait AttachmentRepository : Send + Sync {
+    async fn get_by_id(&self, task_id: Uuid, id: Uuid) -> Result<AttachmentEntity, Error>;
+    async fn get_by_task(&self, task_id: Uuid) -> Vec<AttachmentEntity>;
+    async fn insert(&self, entity: AttachmentEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn delete(&self, task_id: Uuid, id: Uuid) -> Result<(), Error>;
+    async fn count_by_hash(&self, hash: &str) -> i64;
+    async fn get_hashes_by_workspace(&self, workspace_id: Uuid) -> Vec<String>; // Trashed tasks included, each hash once
+    async fn lock_hash(&self, hash: &str) -> Result<Box<dyn HashLock>, Error>; // Waits while another upload or removal of the same content holds it
+}
+
+// Dropping the lock releases it as well, so an early return can't leave it behind
+#[async_trait]
+pub trait HashLock : Send {
+    async fn release(self: Box<Self>);
+}
+
+// Content addressed file storage, files are keyed by their hash
+#[async_trait]
+pub trait FileStorage : Send + Sync {
+    async fn save(&self, hash: &str, content: &[u8]) -> Result<(), Error>; // No-op if the file already exists
+    async fn load(&self, hash: &str) -> Result<Vec<u8>, Error>;
+    async fn remove(&self, hash: &str) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait ChecklistRepository : Send + Sync {
+    async fn get_by_id(&self, task_id: Uuid, id: Uuid) -> Result<ChecklistItemEntity, Error>;
+    async fn get_by_task(&self, task_id: Uuid) -> Vec<ChecklistItemEntity>; // Ordered by position
+    async fn insert(&self, entity: ChecklistItemEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn set_done(&self, task_id: Uuid, id: Uuid, done: bool) -> Result<(), Error>;
+    async fn set_positions(&self, task_id: Uuid, ordered_ids: &[Uuid]) -> Result<(), Error>; // Position of an item is its index in the slice
+    async fn delete(&self, task_id: Uuid, id: Uuid) -> Result<(), Error>;
+}
+
+// Edges of the dependency graph, blocker has to be finished before the blocked task
+#[async_trait]
+pub trait DependencyRepository : Send + Sync {
+    async fn add(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), Error>;
+    async fn remove(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), Error>;
+    async fn get_blockers(&self, task_id: Uuid) -> Vec<TaskEntity>;
+    async fn get_dependents(&self, task_id: Uuid) -> Vec<TaskEntity>;
+    async fn get_all_dependents_recursive(&self, task_id: Uuid) -> Vec<Uuid>;
+}
+
+#[async_trait]
+pub trait RecurrenceRepository : Send + Sync {
+    async fn get_by_task(&self, task_id: Uuid) -> Option<RecurrenceEntity>;
+    async fn upsert(&self, entity: RecurrenceEntity) -> Result<(), Error>; // Only rule and clone flag are replaced on an existing row
+    async fn set_next(&self, task_id: Uuid, next_task_id: Uuid) -> Result<(), Error>;
+    async fn delete(&self, task_id: Uuid) -> Result<(), Error>;
+}
+
+// Not scoped by workspace, the scheduler looks at every task
+#[async_trait]
+pub trait ReminderRepository : Send + Sync {
+    async fn get_unfinished_due_before(&self, until: DateTime<Utc>) -> Vec<TaskEntity>;
+    async fn try_insert(&self, entity: ReminderEntity) -> Result<bool, Error>; // False if the same reminder was fired already
+    async fn get_by_task(&self, task_id: Uuid) -> Vec<ReminderEntity>;
+}
+
+// Where fired reminders are delivered to
+#[async_trait]
+pub trait ReminderChannel : Send + Sync {
+    fn name(&self) -> &str;
+    async fn deliver(&self, reminder: &ReminderDto) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait NotificationRepository : Send + Sync {
+    async fn get_preferences(&self, email: &str) -> Option<NotificationPreferenceEntity>;
+    async fn upsert_preferences(&self, entity: NotificationPreferenceEntity) -> Result<(), Error>;
+    async fn enqueue(&self, entity: OutboxMessageEntity) -> Result<(), Error>; // Consumes ownership. After insert T should not be used
+    async fn get_pending(&self, now: DateTime<Utc>, max_attempts: i32, take: i32) -> Vec<OutboxMessageEntity>; // Unsent, non-digest messages due for an attempt
+    async fn mark_sent(&self, id: Uuid, sent_date: DateTime<Utc>) -> Result<(), Error>;
+    async fn mark_failed(&self, id: Uuid, next_attempt_date: DateTime<Utc>, error: &str) -> Result<(), Error>; // Also counts the attempt
+    async fn get_digest_items(&self, created_before: DateTime<Utc>) -> Vec<OutboxMessageEntity>; // Oldest first
+}
+
+#[async_trait]
+pub trait Mailer : Send + Sync {
+    async fn send(&self, to: &str, subject: &str, text_body: &str, html_body: Option<&str>) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait WorkflowRepository : Send + Sync {
+    async fn get_transitions(&self, workspace_id: Uuid) -> Vec<WorkflowTransitionEntity>;
+    async fn get_hooks(&self, workspace_id: Uuid) -> Vec<WorkflowHookEntity>;
+    async fn replace(&self, workspace_id: Uuid, transitions: Vec<WorkflowTransitionEntity>, hooks: Vec<WorkflowHookEntity>) -> Result<(), Error>; // All or nothing
+}
+
+#[async_trait]
+pub trait TemplateRepository : Send + Sync {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TemplateEntity, Error>;
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<TemplateEntity>;
+    async fn get_nodes(&self, template_id: Uuid) -> Vec<TemplateNodeEntity>; // Ordered by position
+    async fn insert(&self, entity: TemplateEntity, nodes: Vec<TemplateNodeEntity>) -> Result<(), Error>; // All or nothing
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, nodes: Vec<TemplateNodeEntity>) -> Result<(), Error>; // Replaces all nodes, all or nothing
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error>;
+}
+
+// Trashed tasks only, everything else in the repos treats them as gone
+#[async_trait]
+pub trait TrashRepository : Send + Sync {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TrashItemEntity, Error>;
+    async fn get_batch(&self, workspace_id: Uuid, take: i32, continuation_token: &str) -> (Vec<TrashItemEntity>, String); // Tops of trashed subtrees, recently deleted first
+    async fn get_subtree(&self, workspace_id: Uuid, id: Uuid) -> Vec<Uuid>; // The task itself and everything trashed below it, empty when it is not in the trash
+    async fn get_expired(&self, deleted_before: DateTime<Utc>) -> Vec<TrashItemEntity>; // Tops of trashed subtrees across all workspaces
+    async fn get_detached(&self, workspace_id: Uuid, id: Uuid) -> Vec<Uuid>; // Subtasks moved away when the task was trashed without them, and not moved since
+    async fn restore(&self, workspace_id: Uuid, ids: &[Uuid], root_task_id: Option<Uuid>, reattached: &[Uuid]) -> Result<(), Error>; // The first id gets `root_task_id` as its parent and `reattached` back as subtasks, all or nothing
+    async fn delete_many(&self, workspace_id: Uuid, ids: &[Uuid]) -> Result<(), Error>; // Removes for good
+}
+
+#[async_trait]
+pub trait IdempotencyRepository : Send + Sync {
+    async fn reserve(&self, workspace_id: Uuid, key: &str, fingerprint: &str, now: DateTime<Utc>, expires_at: DateTime<Utc>) -> Result<bool, Error>; // False when the key is taken and not expired yet
+    async fn get_by_key(&self, workspace_id: Uuid, key: &str) -> Option<IdempotencyKeyEntity>;
+    async fn complete(&self, workspace_id: Uuid, key: &str, status_code: i16, content_type: Option<&str>, body: &[u8]) -> Result<(), Error>;
+    async fn delete(&self, workspace_id: Uuid, key: &str) -> Result<(), Error>;
+    async fn delete_expired(&self, now: DateTime<Utc>) -> u64;
+}
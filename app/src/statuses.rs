@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use domain::{models::{StatusEntity, PriorityEntity}, enums::StatusCategory};
+
+use uuid::Uuid;
+
+use crate::{dtos::{StatusDto, UpsertStatusDto, PriorityDto, UpsertPriorityDto, TaskAction}, repos::{StatusRepository, PriorityRepository}, errors::Error, logs::LogService};
+
+// Every new workspace starts with these, the same set the former fixed values were migrated to
+const DEFAULT_STATUSES: [(&str, &str, StatusCategory); 4] = [
+    ("Reserved", "#9e9e9e", StatusCategory::Todo),
+    ("Ongoing", "#1e90ff", StatusCategory::InProgress),
+    ("Pending", "#f9a825", StatusCategory::Todo),
+    ("Done", "#2e7d32", StatusCategory::Done),
+];
+
+const DEFAULT_PRIORITIES: [(&str, &str); 4] = [
+    ("Low", "#8bc34a"),
+    ("Normal", "#1e90ff"),
+    ("High", "#ff9800"),
+    ("Urgent", "#f44336"),
+];
+
+pub struct StatusService {
+    repo: Arc<dyn StatusRepository>,
+    priority_repo: Arc<dyn PriorityRepository>,
+    log_service: Arc<LogService>
+}
+
+impl StatusService {
+    pub fn new(repo: Arc<dyn StatusRepository>, priority_repo: Arc<dyn PriorityRepository>, log_service: Arc<LogService>) -> StatusService {
+        StatusService { repo, priority_repo, log_service }
+    }
+
+    // Stored by the workspace repository together with the workspace itself
+    pub fn defaults(&self, workspace_id: Uuid) -> (Vec<StatusEntity>, Vec<PriorityEntity>) {
+        let statuses = DEFAULT_STATUSES.iter().enumerate()
+            .map(|(position, (name, color, category))| StatusEntity { id: Uuid::new_v4(), workspace_id, name: name.to_string(), color: color.to_string(), position: position as i32, category: *category })
+            .collect();
+
+        let priorities = DEFAULT_PRIORITIES.iter().enumerate()
+            .map(|(position, (name, color))| PriorityEntity { id: Uuid::new_v4(), workspace_id, name: name.to_string(), color: color.to_string(), position: position as i32 })
+            .collect();
+
+        (statuses, priorities)
+    }
+
+    pub async fn get_statuses(&self, workspace_id: Uuid) -> Vec<StatusDto> {
+        let entities = self.repo.get_all(workspace_id).await;
+
+        entities.iter().map(StatusDto::new).collect()
+    }
+
+    pub async fn get_all_statuses(&self, workspace_id: Uuid) -> Vec<StatusEntity> {
+        self.repo.get_all(workspace_id).await
+    }
+
+    // Names are matched case insensitively, tasks refer to statuses by name
+    pub async fn resolve_status(&self, workspace_id: Uuid, name: &str) -> Result<StatusEntity, Error> {
+        self.repo.get_all(workspace_id).await
+            .into_iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or(Error::InvalidInput(format!("Unknown status: {}", name)))
+    }
+
+    // Status new tasks start in when nobody picked one, e.g. the next occurrence of a recurring task
+    pub async fn initial_status(&self, workspace_id: Uuid) -> Result<StatusEntity, Error> {
+        let statuses = self.repo.get_all(workspace_id).await;
+        let first_todo = statuses.iter().position(|s| s.category == StatusCategory::Todo).unwrap_or(0);
+
+        statuses.into_iter().nth(first_todo).ok_or(Error::invalid_input("Workspace has no statuses"))
+    }
+
+    pub async fn create_status(&self, workspace_id: Uuid, details: &UpsertStatusDto) -> Result<Uuid, Error> {
+        let existing = self.repo.get_all(workspace_id).await;
+        validate(&details.name, &details.color, existing.iter().map(|s| (s.id, s.name.as_str())), None)?;
+
+        let id = Uuid::new_v4();
+        let entity = StatusEntity {
+            id,
+            workspace_id,
+            name: details.name.trim().to_string(),
+            color: details.color.to_lowercase(),
+            position: details.position.unwrap_or(existing.iter().map(|s| s.position + 1).max().unwrap_or(0)),
+            category: details.category.as_model()
+        };
+
+        self.repo.insert(entity).await?;
+        self.log_service.log_task_action(TaskAction::Create, Some(id), Some("StatusEntity"), None).await;
+
+        Ok(id)
+    }
+
+    pub async fn update_status(&self, workspace_id: Uuid, id: Uuid, details: &UpsertStatusDto) -> Result<(), Error> {
+        let entity = self.repo.get_by_id(workspace_id, id).await?;
+        let existing = self.repo.get_all(workspace_id).await;
+        validate(&details.name, &details.color, existing.iter().map(|s| (s.id, s.name.as_str())), Some(id))?;
+
+        self.repo.update(workspace_id, id, details.name.trim(), &details.color.to_lowercase(), details.position.unwrap_or(entity.position), details.category.as_model()).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(id), Some("StatusEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Workflow transitions through the status are removed by the database (ON DELETE CASCADE)
+    pub async fn delete_status(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.repo.get_by_id(workspace_id, id).await?;
+
+        let used_by = self.repo.count_tasks(id).await;
+        if used_by > 0 {
+            return Err(Error::Conflict(format!("Status is used by {} task(s)", used_by)));
+        }
+
+        self.repo.delete(workspace_id, id).await?;
+        self.log_service.log_task_action(TaskAction::Delete, Some(id), Some("StatusEntity"), None).await;
+
+        Ok(())
+    }
+
+    pub async fn get_priorities(&self, workspace_id: Uuid) -> Vec<PriorityDto> {
+        let entities = self.priority_repo.get_all(workspace_id).await;
+
+        entities.iter().map(PriorityDto::new).collect()
+    }
+
+    pub async fn resolve_priority(&self, workspace_id: Uuid, name: &str) -> Result<PriorityEntity, Error> {
+        self.priority_repo.get_all(workspace_id).await
+            .into_iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or(Error::InvalidInput(format!("Unknown priority: {}", name)))
+    }
+
+    pub async fn create_priority(&self, workspace_id: Uuid, details: &UpsertPriorityDto) -> Result<Uuid, Error> {
+        let existing = self.priority_repo.get_all(workspace_id).await;
+        validate(&details.name, &details.color, existing.iter().map(|p| (p.id, p.name.as_str())), None)?;
+
+        let id = Uuid::new_v4();
+        let entity = PriorityEntity {
+            id,
+            workspace_id,
+            name: details.name.trim().to_string(),
+            color: details.color.to_lowercase(),
+            position: details.position.unwrap_or(existing.iter().map(|p| p.position + 1).max().unwrap_or(0))
+        };
+
+        self.priority_repo.insert(entity).await?;
+        self.log_service.log_task_action(TaskAction::Create, Some(id), Some("PriorityEntity"), None).await;
+
+        Ok(id)
+    }
+
+    pub async fn update_priority(&self, workspace_id: Uuid, id: Uuid, details: &UpsertPriorityDto) -> Result<(), Error> {
+        let entity = self.priority_repo.get_by_id(workspace_id, id).await?;
+        let existing = self.priority_repo.get_all(workspace_id).await;
+        validate(&details.name, &details.color, existing.iter().map(|p| (p.id, p.name.as_str())), Some(id))?;
+
+        self.priority_repo.update(workspace_id, id, details.name.trim(), &details.color.to_lowercase(), details.position.unwrap_or(entity.position)).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(id), Some("PriorityEntity"), None).await;
+
+        Ok(())
+    }
+
+    pub async fn delete_priority(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.priority_repo.get_by_id(workspace_id, id).await?;
+
+        let used_by = self.priority_repo.count_tasks(id).await;
+        if used_by > 0 {
+            return Err(Error::Conflict(format!("Priority is used by {} task(s)", used_by)));
+        }
+
+        self.priority_repo.delete(workspace_id, id).await?;
+        self.log_service.log_task_action(TaskAction::Delete, Some(id), Some("PriorityEntity"), None).await;
+
+        Ok(())
+    }
+}
+
+// `existing` are (id, name) pairs of the workspace, `current` is the one being updated
+fn validate<'a>(name: &str, color: &str, mut existing: impl Iterator<Item = (Uuid, &'a str)>, current: Option<Uuid>) -> Result<(), Error> {
+    if name.trim().is_empty() {
+        return Err(Error::invalid_input("Name can't be empty"));
+    }
+
+    // Same css-like hex strings as tags use, e.g. #1e90ff
+    let bytes = color.as_bytes();
+    if bytes.len() != 7 || bytes[0] != b'#' || !bytes[1..].iter().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::invalid_input("Color must be a hex string like #1e90ff"));
+    }
+
+    if existing.any(|(id, n)| Some(id) != current && n.eq_ignore_ascii_case(name.trim())) {
+        return Err(Error::Conflict(format!("{} already exists", name.trim())));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use domain::models::TagEntity;
+
+use uuid::Uuid;
+
+use crate::{dtos::{TagDto, UpsertTagDto, TaskAction}, repos::TagRepository, errors::Error, logs::LogService};
+
+pub struct TagService {
+    repo: Arc<dyn TagRepository>,
+    log_service: Arc<LogService>
+}
+
+impl TagService {
+    pub fn new(repo: Arc<dyn TagRepository>, log_service: Arc<LogService>) -> TagService {
+        TagService { repo, log_service }
+    }
+
+    pub async fn get_tags(&self, workspace_id: Uuid) -> Vec<TagDto> {
+        let entities = self.repo.get_all(workspace_id).await;
+
+        entities.iter().map(TagDto::new).collect()
+    }
+
+    pub async fn get_tag(&self, workspace_id: Uuid, id: Uuid) -> Result<TagDto, Error> {
+        let entity = self.repo.get_by_id(workspace_id, id).await?;
+
+        Ok(TagDto::new(&entity))
+    }
+
+    pub async fn create_tag(&self, workspace_id: Uuid, details: &UpsertTagDto) -> Result<Uuid, Error> {
+        validate(details)?;
+
+        let id = Uuid::new_v4();
+        let entity = TagEntity {
+            id,
+            workspace_id,
+            name: details.name.trim().to_string(),
+            color: details.color.to_lowercase()
+        };
+
+        self.repo.insert(entity).await?;
+        self.log_service.log_task_action(TaskAction::Create, Some(id), Some("TagEntity"), None).await;
+
+        Ok(id)
+    }
+
+    pub async fn update_tag(&self, workspace_id: Uuid, id: Uuid, details: &UpsertTagDto) -> Result<(), Error> {
+        validate(details)?;
+
+        self.repo.update(workspace_id, id, details.name.trim(), &details.color.to_lowercase()).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(id), Some("TagEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Links to tasks are removed by the database (ON DELETE CASCADE)
+    pub async fn delete_tag(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.repo.delete(workspace_id, id).await?;
+        self.log_service.log_task_action(TaskAction::Delete, Some(id), Some("TagEntity"), None).await;
+
+        Ok(())
+    }
+}
+
+fn validate(details: &UpsertTagDto) -> Result<(), Error> {
+    if details.name.trim().is_empty() {
+        return Err(Error::invalid_input("Tag name can't be empty"));
+    }
+
+    // Colors are kept as css-like hex strings, e.g. #1e90ff
+    let color = details.color.as_bytes();
+    if color.len() != 7 || color[0] != b'#' || !color[1..].iter().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::invalid_input("Tag color must be a hex string like #1e90ff"));
+    }
+
+    Ok(())
+}
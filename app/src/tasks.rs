@@ -1,51 +1,126 @@
-use std::sync::Arc;
+use std::{sync::Arc, collections::HashMap};
 
-use domain::models::TaskEntity;
+use domain::{models::{TaskEntity, TagEntity, StatusEntity, PriorityEntity}, enums::StatusCategory};
 
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 
-use crate::{dtos::{TaskFullDto, UpsertTaskDto, TaskSearchDto, TaskDetailedDto, TaskAction}, repos::TaskRepository, errors::Error, logs::LogService};
+use crate::{dtos::{TaskFullDto, UpsertTaskDto, TaskSearchDto, TaskDetailedDto, DuplicateTaskDto, DeleteMode, DeleteSummaryDto, MoveTaskDto, TaskPatchDto, ExpectedVersion, BulkRequestDto, BulkOperationDto, BulkSummaryDto, BulkItemResultDto, BulkItemStatus, BulkMode, TaskAction}, repos::{TaskRepository, WorkspaceRepository, TagRepository, ChecklistRepository, DependencyRepository, TaskFilter, TaskChanges, TaskWrite}, errors::Error, logs::LogService, recurrence::RecurrenceService, notifications::{NotificationService, validate_email}, workflow::{WorkflowService, StatusHook}, statuses::StatusService, ranks};
+
+// Above that a bulk request is refused as a whole
+const MAX_BULK_OPERATIONS: usize = 100;
+
+// An update that passed every check, ready to be written
+struct PreparedUpdate {
+    entity: TaskEntity,
+    priority: PriorityEntity,
+    status: StatusEntity,
+    hooks: Vec<StatusHook>,
+    finishing: bool,
+    reopening: bool,
+    guard: Option<i32>, // version the row must still have when written
+}
+
+// A delete that passed every check, with the tasks it is going to touch
+struct PreparedDelete {
+    mode: DeleteMode,
+    deleted: Vec<Uuid>, // the task first
+    moved: Vec<Uuid>,
+    new_root_id: Option<Uuid>,
+    guard: Option<i32>,
+}
+
+impl PreparedDelete {
+    fn write(&self) -> TaskWrite {
+        match self.mode {
+            DeleteMode::Cascade => TaskWrite::Trash { ids: self.deleted.clone(), expected_version: self.guard },
+            _ => TaskWrite::TrashKeepingSubtasks { id: self.deleted[0], new_root_id: self.new_root_id, expected_version: self.guard }
+        }
+    }
+}
+
+// What is left to do for an operation of an all-or-nothing bulk request once the transaction has been committed
+enum BulkFollowUp {
+    Update(Box<PreparedUpdate>),
+    Root(Uuid),
+    Delete(PreparedDelete),
+    TagAdded(Uuid, Uuid),
+    TagRemoved(Uuid, Uuid),
+}
 
 pub struct TaskService {
     repo: Arc<dyn TaskRepository>,
-    log_service: Arc<LogService>
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    tag_repo: Arc<dyn TagRepository>,
+    checklist_repo: Arc<dyn ChecklistRepository>,
+    dependency_repo: Arc<dyn DependencyRepository>,
+    log_service: Arc<LogService>,
+    recurrence_service: Arc<RecurrenceService>,
+    notification_service: Arc<NotificationService>,
+    workflow_service: Arc<WorkflowService>,
+    status_service: Arc<StatusService>,
+    enforce_blockers: bool, // refuse to finish a task while any of its blockers is not done
+    require_if_match: bool // refuse writes that don't say which version of the task they are based on
 }
 
 impl TaskService {
-    pub fn new(repo: Arc<dyn TaskRepository>, log_service: Arc<LogService>) -> TaskService {
-        TaskService { repo, log_service }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(repo: Arc<dyn TaskRepository>, workspace_repo: Arc<dyn WorkspaceRepository>, tag_repo: Arc<dyn TagRepository>, checklist_repo: Arc<dyn ChecklistRepository>, dependency_repo: Arc<dyn DependencyRepository>, log_service: Arc<LogService>, recurrence_service: Arc<RecurrenceService>, notification_service: Arc<NotificationService>, workflow_service: Arc<WorkflowService>, status_service: Arc<StatusService>, enforce_blockers: bool, require_if_match: bool) -> TaskService {
+        TaskService { repo, workspace_repo, tag_repo, checklist_repo, dependency_repo, log_service, recurrence_service, notification_service, workflow_service, status_service, enforce_blockers, require_if_match }
     }
 
-    pub async fn get_root_task_batch(&self, take: i32, continuation_token: &str, sort_by: &str, descending: bool) -> (Vec<TaskDetailedDto>, String) {
-        let (entities, ct) = self.repo.get_root_task_batch(take, continuation_token, sort_by, descending).await;
+    pub async fn get_root_task_batch(&self, workspace_id: Uuid, filter: &TaskFilter, take: i32, continuation_token: &str, sort_by: &str, descending: bool) -> (Vec<TaskDetailedDto>, String) {
+        let (entities, ct) = self.repo.get_root_task_batch(workspace_id, filter, take, continuation_token, sort_by, descending).await;
+        let mut tags = self.get_tags_by_task(&entities).await;
 
-        (entities.iter().map(|e| TaskDetailedDto::new(e)).collect(), ct)
+        (entities.iter().map(|e| TaskDetailedDto::new(e, &tags.remove(&e.id).unwrap_or_default())).collect(), ct)
     }
 
-    pub async fn get_task(&self, id: Uuid) -> Result<TaskFullDto, Error> {
-        let entity = self.repo.get_by_id(id).await?;
-        let root_entity = match entity.root_task_id {
-            Some(root_id) => Some(self.repo.get_by_id(root_id).await.unwrap()),
-            None => None
-        };
+    pub async fn get_task(&self, workspace_id: Uuid, id: Uuid) -> Result<TaskFullDto, Error> {
+        let entity = self.repo.get_by_id(workspace_id, id).await?;
+        let ancestors = self.repo.get_ancestors(workspace_id, id).await;
+        let root_entity = ancestors.last();
+
+        let subtasks = self.repo.get_subtasks(workspace_id, id).await;
+        let tags = self.get_tags_by_task(std::slice::from_ref(&entity)).await.remove(&id).unwrap_or_default();
+        let checklist = self.checklist_repo.get_by_task(id).await;
+        let blockers = self.dependency_repo.get_blockers(id).await;
+        let dependents = self.dependency_repo.get_dependents(id).await;
+        let recurrence = self.recurrence_service.get_by_task(id).await;
+        let progress = self.repo.count_subtasks_by_status(workspace_id, id).await;
 
-        let subtasks = self.repo.get_subtasks(id).await;
-        
-        Ok(TaskFullDto::new(&entity, root_entity.as_ref(), &subtasks))
+        Ok(TaskFullDto::new(&entity, root_entity, &subtasks, &tags, &checklist, &blockers, &dependents)
+            .with_breadcrumbs(&ancestors)
+            .with_recurrence(recurrence.as_ref())
+            .with_progress(&progress))
     }
 
-    pub async fn create_task(&self, details: &UpsertTaskDto) -> Result<Uuid, Error> {
+    pub async fn create_task(&self, workspace_id: Uuid, details: &UpsertTaskDto) -> Result<Uuid, Error> {
+        self.workspace_repo.get_by_id(workspace_id).await?;
+
+        let priority = self.status_service.resolve_priority(workspace_id, &details.priority).await?;
+        let status = self.status_service.resolve_status(workspace_id, &details.status).await?;
+
+        // New tasks go to the end of the list
+        let last_rank = self.repo.get_last_rank(workspace_id, None).await;
+
         let id = Uuid::new_v4();
         let entity = TaskEntity {
             id,
+            workspace_id,
             root_task_id: None,
             summary: details.summary.clone(),
             description: details.description.clone(),
             create_date: Utc::now(),
             due_date: details.due_date,
-            priority: details.priority.as_model(),
-            status: details.status.as_model()
+            assignee: None,
+            resolution: None,
+            auto_complete: false,
+            archived_at: None,
+            rank: ranks::between(last_rank.as_deref(), None),
+            version: 1,
+            priority,
+            status
         };
 
         self.repo.insert(entity).await?;
@@ -54,43 +129,763 @@ impl TaskService {
         Ok(id)
     }
 
-    pub async fn update_task(&self, task_id: Uuid, details: &UpsertTaskDto) -> Result<(), Error>{
-        self.repo
-            .update_task(task_id, details.summary.as_str(), details.description.as_deref(), details.due_date, details.priority.as_model(), details.status.as_model())
-            .await?;
+    // Creates a whole tree in one transaction. Every item names its parent by index, which has to point at an earlier item,
+    // items without one go under `root_id`. Ids come back in the order of the items
+    pub async fn create_task_tree(&self, workspace_id: Uuid, root_id: Option<Uuid>, items: &[(Option<usize>, UpsertTaskDto)]) -> Result<Vec<Uuid>, Error> {
+        self.workspace_repo.get_by_id(workspace_id).await?;
+
+        if let Some(root_id) = root_id {
+            // Lookups are workspace scoped, so a root from another workspace is simply not found
+            if self.repo.get_by_id(workspace_id, root_id).await.is_err() {
+                return Err(Error::invalid_root_binding("Can't bind task to a task from another workspace"));
+            }
+        }
+
+        // Items keep their order among the siblings, the top ones go after the existing subtasks of `root_id`
+        let mut last_ranks: HashMap<Option<Uuid>, String> = HashMap::new();
+        if let Some(rank) = self.repo.get_last_rank(workspace_id, root_id).await {
+            last_ranks.insert(root_id, rank);
+        }
+
+        let mut ids: Vec<Uuid> = vec![];
+        let mut entities: Vec<TaskEntity> = vec![];
+        for (index, (parent, details)) in items.iter().enumerate() {
+            let root_task_id = match parent {
+                Some(p) if *p < index => Some(ids[*p]),
+                Some(_) => return Err(Error::invalid_input("Parent has to come before its subtasks")),
+                None => root_id
+            };
+
+            let rank = ranks::between(last_ranks.get(&root_task_id).map(|r| r.as_str()), None);
+            last_ranks.insert(root_task_id, rank.clone());
+
+            let id = Uuid::new_v4();
+            entities.push(TaskEntity {
+                id,
+                workspace_id,
+                root_task_id,
+                summary: details.summary.clone(),
+                description: details.description.clone(),
+                create_date: Utc::now(),
+                due_date: details.due_date,
+                assignee: None,
+                resolution: None,
+                auto_complete: false,
+                archived_at: None,
+                rank,
+                version: 1,
+                priority: self.status_service.resolve_priority(workspace_id, &details.priority).await?,
+                status: self.status_service.resolve_status(workspace_id, &details.status).await?
+            });
+            ids.push(id);
+        }
+
+        self.repo.insert_many(entities).await?;
+
+        for id in &ids {
+            self.log_service.log_task_action(TaskAction::Create, Some(*id), Some("TaskEntity"), None).await;
+        }
+
+        Ok(ids)
+    }
+
+    // Only the fields present in the patch are written, so concurrent changes of other fields are not lost
+    pub async fn update_task(&self, workspace_id: Uuid, task_id: Uuid, patch: &TaskPatchDto, expected_version: Option<&ExpectedVersion>) -> Result<(), Error>{
+        let prepared = self.prepare_update(workspace_id, task_id, patch, expected_version).await?;
+        if patch.is_empty() {
+            return Ok(());
+        }
+
+        self.repo.update_task(workspace_id, task_id, &task_changes(patch, &prepared)).await?;
+
+        self.finish_update(workspace_id, prepared).await
+    }
+
+    // Logging and everything set off by a status change, run once the update has been written
+    async fn finish_update(&self, workspace_id: Uuid, prepared: PreparedUpdate) -> Result<(), Error> {
+        let PreparedUpdate { entity, status, hooks, finishing, reopening, .. } = prepared;
+        let task_id = entity.id;
 
         self.log_service.log_task_action(TaskAction::Update, Some(task_id), Some("TaskEntity"), None).await;
 
+        if entity.status.id != status.id {
+            let payload = format!("{} -> {}", entity.status.name, status.name);
+            self.log_service.log_task_action(TaskAction::StatusChanged, Some(task_id), Some("TaskEntity"), Some(&payload)).await;
+
+            for hook in hooks {
+                self.run_hook(workspace_id, task_id, hook).await?;
+            }
+
+            let updated = self.repo.get_by_id(workspace_id, task_id).await?;
+            self.notification_service.notify_status_changed(&updated, &entity.status).await?;
+
+            if finishing {
+                self.recurrence_service.generate_next(&updated).await?;
+            }
+
+            if finishing || reopening {
+                self.roll_up(workspace_id, &updated).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Every check of an update without writing anything, bulk requests run it up front for all of their items
+    async fn prepare_update(&self, workspace_id: Uuid, task_id: Uuid, patch: &TaskPatchDto, expected_version: Option<&ExpectedVersion>) -> Result<PreparedUpdate, Error> {
+        let entity = self.repo.get_by_id(workspace_id, task_id).await?;
+        let guard = self.check_version(&entity, expected_version)?;
+
+        let priority = match &patch.priority {
+            Some(name) => self.status_service.resolve_priority(workspace_id, name).await?,
+            None => entity.priority.clone()
+        };
+        let status = match &patch.status {
+            Some(name) => self.status_service.resolve_status(workspace_id, name).await?,
+            None => entity.status.clone()
+        };
+
+        let finishing = entity.status.category != StatusCategory::Done && status.category == StatusCategory::Done;
+        let reopening = entity.status.category == StatusCategory::Done && status.category != StatusCategory::Done;
+
+        // Fields as they will be after the update
+        let resolution = match &patch.resolution {
+            Some(resolution) => resolution.as_deref(),
+            None => entity.resolution.as_deref()
+        };
+        let description = match &patch.description {
+            Some(description) => Some(description.as_str()),
+            None => entity.description.as_deref()
+        };
+        let filled_fields = filled_fields(resolution, description, entity.assignee.as_deref());
+
+        let hooks = self.workflow_service.check_transition(workspace_id, &entity.status, &status, &filled_fields).await?;
+
+        if self.enforce_blockers && finishing {
+            let unfinished = self.dependency_repo.get_blockers(task_id).await
+                .into_iter()
+                .filter(|b| b.status.category != StatusCategory::Done)
+                .count();
+
+            if unfinished > 0 {
+                return Err(Error::Conflict(format!("Task is blocked by {} unfinished task(s)", unfinished)));
+            }
+        }
+
+        Ok(PreparedUpdate { entity, priority, status, hooks, finishing, reopening, guard })
+    }
+
+    // Returns the version to guard the write with, none when any version will do
+    fn check_version(&self, entity: &TaskEntity, expected_version: Option<&ExpectedVersion>) -> Result<Option<i32>, Error> {
+        match expected_version {
+            None if self.require_if_match => Err(Error::PreconditionRequired("Version of the task is required".to_string())),
+            None => Ok(None),
+            Some(ExpectedVersion::Any) => Ok(None),
+            Some(ExpectedVersion::OneOf(versions)) if versions.contains(&entity.version) => Ok(Some(entity.version)),
+            Some(ExpectedVersion::OneOf(_)) => Err(Error::precondition_failed(entity.id))
+        }
+    }
+
+    // In best-effort mode every operation goes through the same path as its single-item endpoint. In all-or-nothing mode all of them
+    // are checked against the current state first, then written in one transaction that the first failing write rolls back
+    pub async fn run_bulk(&self, workspace_id: Uuid, request: &BulkRequestDto) -> Result<BulkSummaryDto, Error> {
+        if request.operations.len() > MAX_BULK_OPERATIONS {
+            return Err(Error::TooLarge(format!("Bulk request can't have more than {} operations", MAX_BULK_OPERATIONS)));
+        }
+
+        let deleted_by = request.deleted_by.as_deref().map(|d| d.trim().to_lowercase()).filter(|d| !d.is_empty());
+        if let Some(email) = &deleted_by {
+            validate_email(email)?;
+        }
+
+        self.workspace_repo.get_by_id(workspace_id).await?;
+
+        if request.mode == BulkMode::AllOrNothing {
+            return self.run_bulk_at_once(workspace_id, request, deleted_by.as_deref()).await;
+        }
+
+        let mut results = vec![];
+        for (index, operation) in request.operations.iter().enumerate() {
+            match self.run_bulk_operation(workspace_id, operation, deleted_by.as_deref()).await {
+                Ok(()) => results.push(BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Ok, None)),
+                Err(err) => results.push(BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Failed, Some(&err)))
+            }
+        }
+
+        Ok(BulkSummaryDto::new(request.mode, results))
+    }
+
+    async fn run_bulk_at_once(&self, workspace_id: Uuid, request: &BulkRequestDto, deleted_by: Option<&str>) -> Result<BulkSummaryDto, Error> {
+        // Moves to the same parent have to line up behind each other, not behind what is there before the request
+        let mut last_ranks = HashMap::new();
+
+        let mut plans = vec![];
+        for operation in &request.operations {
+            plans.push(self.plan_bulk_operation(workspace_id, operation, &mut last_ranks).await);
+        }
+
+        if plans.iter().any(|p| p.is_err()) {
+            let results = request.operations.iter().zip(plans).enumerate()
+                .map(|(index, (operation, plan))| match plan {
+                    Ok(_) => BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Skipped, None),
+                    Err(err) => BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Failed, Some(&err))
+                })
+                .collect();
+
+            return Ok(BulkSummaryDto::new(request.mode, results));
+        }
+
+        // Operations with nothing to write, like an empty patch, have no place among the writes
+        let mut writes = vec![];
+        let mut write_indexes = vec![];
+        let mut follow_ups = vec![];
+        for (index, plan) in plans.into_iter().enumerate() {
+            if let Ok(Some((write, follow_up))) = plan {
+                writes.push(write);
+                write_indexes.push(index);
+                follow_ups.push(follow_up);
+            }
+        }
+
+        if let Err((failed, err)) = self.repo.write_all(workspace_id, &writes, Utc::now(), deleted_by).await {
+            let Some(&failed) = write_indexes.get(failed) else {
+                return Err(err);
+            };
+
+            let results = request.operations.iter().enumerate()
+                .map(|(index, operation)| match index == failed {
+                    true => BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Failed, Some(&err)),
+                    false => BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Skipped, None)
+                })
+                .collect();
+
+            return Ok(BulkSummaryDto::new(request.mode, results));
+        }
+
+        // The writes are in, a follow-up failing now can't take them back
+        let mut results: Vec<BulkItemResultDto> = request.operations.iter().enumerate()
+            .map(|(index, operation)| BulkItemResultDto::new(index, operation.id(), BulkItemStatus::Ok, None))
+            .collect();
+
+        for (index, follow_up) in write_indexes.into_iter().zip(follow_ups) {
+            if let Err(err) = self.finish_bulk_operation(workspace_id, follow_up).await {
+                results[index] = BulkItemResultDto::new(index, request.operations[index].id(), BulkItemStatus::Failed, Some(&err));
+            }
+        }
+
+        Ok(BulkSummaryDto::new(request.mode, results))
+    }
+
+    // Runs the checks of the single-item endpoint and turns the operation into its write, none when there is nothing to write
+    async fn plan_bulk_operation(&self, workspace_id: Uuid, operation: &BulkOperationDto, last_ranks: &mut HashMap<Option<Uuid>, Option<String>>) -> Result<Option<(TaskWrite, BulkFollowUp)>, Error> {
+        let expected_version = operation.expected_version();
+        let expected_version = expected_version.as_ref();
+
+        let patch = match operation {
+            BulkOperationDto::Update { details, .. } => Some(details.clone()),
+            BulkOperationDto::Status { status, resolution, .. } => Some(status_patch(status, resolution.as_deref())),
+            BulkOperationDto::Priority { priority, .. } => Some(priority_patch(priority)),
+            _ => None
+        };
+
+        if let Some(patch) = patch {
+            let prepared = self.prepare_update(workspace_id, operation.id(), &patch, expected_version).await?;
+            if patch.is_empty() {
+                return Ok(None);
+            }
+
+            let write = TaskWrite::Update { id: operation.id(), changes: task_changes(&patch, &prepared) };
+            return Ok(Some((write, BulkFollowUp::Update(Box::new(prepared)))));
+        }
+
+        match operation {
+            BulkOperationDto::Move { id, root_id, .. } => {
+                let entity = self.repo.get_by_id(workspace_id, *id).await?;
+                let guard = self.check_version(&entity, expected_version)?;
+                self.check_root_binding(workspace_id, *id, *root_id).await?;
+
+                let last_rank = match last_ranks.get(root_id) {
+                    Some(rank) => rank.clone(),
+                    None => self.repo.get_last_rank(workspace_id, *root_id).await
+                };
+                let rank = ranks::between(last_rank.as_deref(), None);
+                last_ranks.insert(*root_id, Some(rank.clone()));
+
+                let write = TaskWrite::Root { id: *id, root_task_id: *root_id, rank, expected_version: guard };
+                Ok(Some((write, BulkFollowUp::Root(*id))))
+            },
+            BulkOperationDto::Delete { id, mode, .. } => {
+                let prepared = self.prepare_delete(workspace_id, *id, mode.unwrap_or(DeleteMode::Orphan), expected_version).await?;
+                Ok(Some((prepared.write(), BulkFollowUp::Delete(prepared))))
+            },
+            BulkOperationDto::Tag { id, tag_id } => {
+                self.repo.get_by_id(workspace_id, *id).await?;
+                self.tag_repo.get_by_id(workspace_id, *tag_id).await?;
+                Ok(Some((TaskWrite::AddTag { id: *id, tag_id: *tag_id }, BulkFollowUp::TagAdded(*id, *tag_id))))
+            },
+            BulkOperationDto::Untag { id, tag_id } => {
+                self.repo.get_by_id(workspace_id, *id).await?;
+                Ok(Some((TaskWrite::RemoveTag { id: *id, tag_id: *tag_id }, BulkFollowUp::TagRemoved(*id, *tag_id))))
+            },
+            _ => unreachable!("updates are planned above")
+        }
+    }
+
+    async fn finish_bulk_operation(&self, workspace_id: Uuid, follow_up: BulkFollowUp) -> Result<(), Error> {
+        match follow_up {
+            BulkFollowUp::Update(prepared) => self.finish_update(workspace_id, *prepared).await?,
+            BulkFollowUp::Root(id) => self.log_service.log_task_action(TaskAction::RootChanged, Some(id), Some("TaskEntity"), None).await,
+            BulkFollowUp::Delete(prepared) => self.finish_delete(&prepared).await,
+            BulkFollowUp::TagAdded(id, tag_id) => self.log_service.log_task_action(TaskAction::TagAdded, Some(id), Some("TaskEntity"), Some(&tag_id.to_string())).await,
+            BulkFollowUp::TagRemoved(id, tag_id) => self.log_service.log_task_action(TaskAction::TagRemoved, Some(id), Some("TaskEntity"), Some(&tag_id.to_string())).await
+        }
+
         Ok(())
     }
 
-    pub async fn update_task_root(&self, task_id: Uuid, new_root_id: Option<Uuid>) -> Result<(), Error> {
+    async fn run_bulk_operation(&self, workspace_id: Uuid, operation: &BulkOperationDto, deleted_by: Option<&str>) -> Result<(), Error> {
+        let expected_version = operation.expected_version();
+        let expected_version = expected_version.as_ref();
+
+        match operation {
+            BulkOperationDto::Update { id, details, .. } => self.update_task(workspace_id, *id, details, expected_version).await,
+            BulkOperationDto::Status { id, status, resolution, .. } => self.update_task(workspace_id, *id, &status_patch(status, resolution.as_deref()), expected_version).await,
+            BulkOperationDto::Priority { id, priority, .. } => self.update_task(workspace_id, *id, &priority_patch(priority), expected_version).await,
+            BulkOperationDto::Move { id, root_id, .. } => self.update_task_root(workspace_id, *id, *root_id, expected_version).await,
+            BulkOperationDto::Delete { id, mode, .. } => self.delete_task(workspace_id, *id, mode.unwrap_or(DeleteMode::Orphan), deleted_by, expected_version).await.map(|_| ()),
+            BulkOperationDto::Tag { id, tag_id } => self.add_tag(workspace_id, *id, *tag_id).await,
+            BulkOperationDto::Untag { id, tag_id } => self.remove_tag(workspace_id, *id, *tag_id).await
+        }
+    }
+
+    // Only root tasks carry the setting, it applies to their whole tree
+    pub async fn set_auto_complete(&self, workspace_id: Uuid, task_id: Uuid, auto_complete: bool) -> Result<(), Error> {
+        let entity = self.repo.get_by_id(workspace_id, task_id).await?;
+        if entity.root_task_id.is_some() {
+            return Err(Error::invalid_input("Auto-completion can only be set on a root task"));
+        }
+
+        self.repo.update_auto_complete(workspace_id, task_id, auto_complete).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(task_id), Some("TaskEntity"), Some(&format!("AutoComplete -> {}", auto_complete))).await;
+
+        Ok(())
+    }
+
+    // Copies start over in the initial status, the source id goes to the log of every copy
+    pub async fn duplicate_task(&self, workspace_id: Uuid, task_id: Uuid, details: &DuplicateTaskDto) -> Result<Uuid, Error> {
+        let source = self.repo.get_by_id(workspace_id, task_id).await?;
+
+        if let Some(root_id) = details.root_id {
+            // Lookups are workspace scoped, so a root from another workspace is simply not found
+            if self.repo.get_by_id(workspace_id, root_id).await.is_err() {
+                return Err(Error::invalid_root_binding("Can't bind task to a task from another workspace"));
+            }
+        }
+
+        let shift = Duration::try_days(details.due_date_shift_days).ok_or(Error::invalid_input("Due date shift is out of range"))?;
+        let status = self.status_service.initial_status(workspace_id).await?;
+        let create_date = Utc::now();
+
+        let copy = |original: &TaskEntity, id: Uuid, root_task_id: Option<Uuid>, rank: String| -> Result<TaskEntity, Error> {
+            Ok(TaskEntity {
+                id,
+                workspace_id,
+                root_task_id,
+                summary: original.summary.clone(),
+                description: original.description.clone(),
+                create_date,
+                due_date: original.due_date.checked_add_signed(shift).ok_or(Error::invalid_input("Due date shift is out of range"))?,
+                assignee: original.assignee.clone(),
+                resolution: None,
+                // Only root tasks carry the setting, a copy placed under a parent drops it
+                auto_complete: root_task_id.is_none() && original.auto_complete,
+                archived_at: None,
+                rank,
+                version: 1,
+                priority: original.priority.clone(),
+                status: status.clone()
+            })
+        };
+
+        // The copy goes to the end of its new parent, copied subtasks keep their order
+        let last_rank = self.repo.get_last_rank(workspace_id, details.root_id).await;
+
+        let copy_id = Uuid::new_v4();
+        let mut copies = vec![(source.id, copy(&source, copy_id, details.root_id, ranks::between(last_rank.as_deref(), None))?)];
+
+        if details.include_subtasks {
+            let subtasks = self.repo.get_subtasks_recursive(workspace_id, task_id).await;
+            let mut children: HashMap<Uuid, Vec<&TaskEntity>> = HashMap::new();
+            for subtask in &subtasks {
+                if let Some(parent_id) = subtask.root_task_id {
+                    children.entry(parent_id).or_default().push(subtask);
+                }
+            }
+
+            // Parents go first, their copies have to exist before the children point at them
+            let mut pending = vec![(task_id, copy_id)];
+            while let Some((original_id, parent_copy_id)) = pending.pop() {
+                for child in children.remove(&original_id).unwrap_or_default() {
+                    let child_copy_id = Uuid::new_v4();
+                    copies.push((child.id, copy(child, child_copy_id, Some(parent_copy_id), child.rank.clone())?));
+                    pending.push((child.id, child_copy_id));
+                }
+            }
+        }
+
+        let created: Vec<(Uuid, Uuid)> = copies.iter().map(|(original_id, c)| (*original_id, c.id)).collect();
+        self.repo.insert_many(copies.into_iter().map(|(_, c)| c).collect()).await?;
+
+        for (original_id, id) in created {
+            self.log_service.log_task_action(TaskAction::Create, Some(id), Some("TaskEntity"), Some(&original_id.to_string())).await;
+        }
+
+        Ok(copy_id)
+    }
+
+    // The task goes to the end of its new siblings
+    pub async fn update_task_root(&self, workspace_id: Uuid, task_id: Uuid, new_root_id: Option<Uuid>, expected_version: Option<&ExpectedVersion>) -> Result<(), Error> {
+        let entity = self.repo.get_by_id(workspace_id, task_id).await?;
+        let guard = self.check_version(&entity, expected_version)?;
+
+        self.check_root_binding(workspace_id, task_id, new_root_id).await?;
+
+        let last_rank = self.repo.get_last_rank(workspace_id, new_root_id).await;
+
+        self.repo.update_task_root(workspace_id, task_id, new_root_id, &ranks::between(last_rank.as_deref(), None), guard).await?;
+        self.log_service.log_task_action(TaskAction::RootChanged, Some(task_id), Some("TaskEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Places the task right before or after a sibling, which may live under another parent, or at the end of the subtasks of `root_id`.
+    // Only the moved task gets a new rank
+    pub async fn move_task(&self, workspace_id: Uuid, task_id: Uuid, details: &MoveTaskDto, expected_version: Option<&ExpectedVersion>) -> Result<(), Error> {
+        let entity = self.repo.get_by_id(workspace_id, task_id).await?;
+        let guard = self.check_version(&entity, expected_version)?;
+
+        let sibling = match (details.before_id, details.after_id) {
+            (Some(_), Some(_)) => return Err(Error::invalid_input("Only one of before_id and after_id can be given")),
+            (Some(id), None) => Some((id, false)),
+            (None, Some(id)) => Some((id, true)),
+            (None, None) => None
+        };
+
+        let (root_id, rank) = match sibling {
+            Some((sibling_id, after)) => {
+                if sibling_id == task_id {
+                    return Err(Error::invalid_input("Can't place task next to itself"));
+                }
+
+                let Ok(sibling) = self.repo.get_by_id(workspace_id, sibling_id).await else {
+                    return Err(Error::invalid_input("Sibling task doesn't exist"));
+                };
+
+                if details.root_id.is_some() && details.root_id != sibling.root_task_id {
+                    return Err(Error::invalid_input("Sibling belongs to another parent"));
+                }
+
+                let neighbour_rank = self.repo.get_adjacent_rank(workspace_id, sibling.root_task_id, &sibling.rank, after, task_id).await;
+                let rank = if after {
+                    ranks::between(Some(&sibling.rank), neighbour_rank.as_deref())
+                } else {
+                    ranks::between(neighbour_rank.as_deref(), Some(&sibling.rank))
+                };
+
+                (sibling.root_task_id, rank)
+            },
+            None => {
+                let last_rank = self.repo.get_last_rank(workspace_id, details.root_id).await;
+                (details.root_id, ranks::between(last_rank.as_deref(), None))
+            }
+        };
+
+        if root_id != entity.root_task_id {
+            self.check_root_binding(workspace_id, task_id, root_id).await?;
+        }
+
+        self.repo.update_task_root(workspace_id, task_id, root_id, &rank, guard).await?;
+
+        if root_id != entity.root_task_id {
+            self.log_service.log_task_action(TaskAction::RootChanged, Some(task_id), Some("TaskEntity"), None).await;
+        } else {
+            let payload = format!("{} -> {}", entity.rank, rank);
+            self.log_service.log_task_action(TaskAction::Reordered, Some(task_id), Some("TaskEntity"), Some(&payload)).await;
+        }
+
+        Ok(())
+    }
+
+    async fn check_root_binding(&self, workspace_id: Uuid, task_id: Uuid, new_root_id: Option<Uuid>) -> Result<(), Error> {
         if task_id == new_root_id.unwrap_or_default() {
             return Err(Error::invalid_root_binding("Can't bind task to itself"));
         }
 
         if let Some(new_root_id_unwrapped) = new_root_id {
-            let flat_subtask_ids = self.repo.get_all_subtasks_recursive(task_id).await;
+            // Lookups are workspace scoped, so a root from another workspace is simply not found
+            if self.repo.get_by_id(workspace_id, new_root_id_unwrapped).await.is_err() {
+                return Err(Error::invalid_root_binding("Can't bind task to a task from another workspace"));
+            }
+
+            let flat_subtask_ids = self.repo.get_all_subtasks_recursive(workspace_id, task_id).await;
             if flat_subtask_ids.contains(&new_root_id_unwrapped) {
                 return Err(Error::invalid_root_binding("Can't bind task to its subtask"));
             }
         }
 
-        self.repo.update_task_root(task_id, new_root_id).await?;
-        self.log_service.log_task_action(TaskAction::RootChanged, Some(task_id), Some("TaskEntity"), None).await;
+        Ok(())
+    }
+
+    // Deleted tasks go to the trash, attachments and the rest stay until the trash is purged
+    pub async fn delete_task(&self, workspace_id: Uuid, task_id: Uuid, mode: DeleteMode, deleted_by: Option<&str>, expected_version: Option<&ExpectedVersion>) -> Result<DeleteSummaryDto, Error> {
+        let deleted_by = deleted_by.map(|d| d.trim().to_lowercase()).filter(|d| !d.is_empty());
+        if let Some(email) = &deleted_by {
+            validate_email(email)?;
+        }
+
+        let prepared = self.prepare_delete(workspace_id, task_id, mode, expected_version).await?;
+        let deleted_at = Utc::now();
+
+        if mode == DeleteMode::Cascade {
+            self.repo.trash_many(workspace_id, &prepared.deleted, deleted_at, deleted_by.as_deref(), prepared.guard).await?;
+        } else {
+            self.repo.trash_keeping_subtasks(workspace_id, task_id, prepared.new_root_id, deleted_at, deleted_by.as_deref(), prepared.guard).await?;
+        }
+
+        self.finish_delete(&prepared).await;
+
+        Ok(DeleteSummaryDto::new(mode, &prepared.deleted, &prepared.moved, prepared.new_root_id))
+    }
+
+    async fn prepare_delete(&self, workspace_id: Uuid, task_id: Uuid, mode: DeleteMode, expected_version: Option<&ExpectedVersion>) -> Result<PreparedDelete, Error> {
+        let entity = self.repo.get_by_id(workspace_id, task_id).await?;
+        let guard = self.check_version(&entity, expected_version)?;
+
+        let mut deleted = vec![task_id];
+        let mut moved = vec![];
+        if mode == DeleteMode::Cascade {
+            deleted.extend(self.repo.get_all_subtasks_recursive(workspace_id, task_id).await);
+        } else {
+            moved = self.repo.get_subtasks(workspace_id, task_id).await.into_iter().map(|s| s.id).collect();
+        }
+
+        let new_root_id = if mode == DeleteMode::Reparent { entity.root_task_id } else { None };
+
+        Ok(PreparedDelete { mode, deleted, moved, new_root_id, guard })
+    }
+
+    async fn finish_delete(&self, prepared: &PreparedDelete) {
+        for id in &prepared.deleted {
+            self.log_service.log_task_action(TaskAction::Delete, Some(*id), Some("TaskEntity"), None).await;
+        }
+
+        for id in &prepared.moved {
+            self.log_service.log_task_action(TaskAction::RootChanged, Some(*id), Some("TaskEntity"), prepared.new_root_id.map(|r| r.to_string()).as_deref()).await;
+        }
+    }
+
+    pub async fn add_tag(&self, workspace_id: Uuid, task_id: Uuid, tag_id: Uuid) -> Result<(), Error> {
+        self.repo.get_by_id(workspace_id, task_id).await?;
+        self.tag_repo.get_by_id(workspace_id, tag_id).await?;
+
+        self.tag_repo.add_to_task(task_id, tag_id).await?;
+        self.log_service.log_task_action(TaskAction::TagAdded, Some(task_id), Some("TaskEntity"), Some(&tag_id.to_string())).await;
 
         Ok(())
     }
 
-    pub async fn delete_task(&self, task_id: Uuid) -> Result<(), Error> {
-        self.repo.delete(task_id).await?;
-        self.log_service.log_task_action(TaskAction::Delete, Some(task_id), Some("TaskEntity"), None).await;
+    pub async fn remove_tag(&self, workspace_id: Uuid, task_id: Uuid, tag_id: Uuid) -> Result<(), Error> {
+        self.repo.get_by_id(workspace_id, task_id).await?;
+
+        self.tag_repo.remove_from_task(task_id, tag_id).await?;
+        self.log_service.log_task_action(TaskAction::TagRemoved, Some(task_id), Some("TaskEntity"), Some(&tag_id.to_string())).await;
 
         Ok(())
     }
 
-    pub async fn search_tasks(&self, phrase: &str, take: i32, continuation_token: &str) -> (Vec<TaskSearchDto>, String) {
-        let (entities, ct) = self.repo.search_tasks(phrase, take, continuation_token).await;
-        (entities.iter().map(|e| TaskSearchDto::new(e)).collect(), ct)
+    pub async fn set_assignee(&self, workspace_id: Uuid, task_id: Uuid, assignee: Option<&str>) -> Result<(), Error> {
+        let assignee = assignee.map(|a| a.trim().to_lowercase()).filter(|a| !a.is_empty());
+        if let Some(email) = &assignee {
+            validate_email(email)?;
+        }
+
+        let mut entity = self.repo.get_by_id(workspace_id, task_id).await?;
+
+        self.repo.update_assignee(workspace_id, task_id, assignee.as_deref()).await?;
+        self.log_service.log_task_action(TaskAction::AssigneeChanged, Some(task_id), Some("TaskEntity"), assignee.as_deref()).await;
+
+        if assignee.is_some() && assignee != entity.assignee {
+            entity.assignee = assignee;
+            self.notification_service.notify_assigned(&entity).await?;
+        }
+
+        Ok(())
     }
-}
\ No newline at end of file
+
+    pub async fn add_blocker(&self, workspace_id: Uuid, task_id: Uuid, blocker_id: Uuid) -> Result<(), Error> {
+        if task_id == blocker_id {
+            return Err(Error::invalid_input("Task can't block itself"));
+        }
+
+        self.repo.get_by_id(workspace_id, task_id).await?;
+        // Lookups are workspace scoped, so a blocker from another workspace is simply not found
+        self.repo.get_by_id(workspace_id, blocker_id).await?;
+
+        // The blocker must not be waiting for the task, directly or through other tasks
+        let flat_dependent_ids = self.dependency_repo.get_all_dependents_recursive(task_id).await;
+        if flat_dependent_ids.contains(&blocker_id) {
+            return Err(Error::invalid_input("Dependency would create a cycle"));
+        }
+
+        self.dependency_repo.add(blocker_id, task_id).await?;
+        self.log_service.log_task_action(TaskAction::DependencyAdded, Some(task_id), Some("TaskEntity"), Some(&blocker_id.to_string())).await;
+
+        Ok(())
+    }
+
+    pub async fn remove_blocker(&self, workspace_id: Uuid, task_id: Uuid, blocker_id: Uuid) -> Result<(), Error> {
+        self.repo.get_by_id(workspace_id, task_id).await?;
+
+        self.dependency_repo.remove(blocker_id, task_id).await?;
+        self.log_service.log_task_action(TaskAction::DependencyRemoved, Some(task_id), Some("TaskEntity"), Some(&blocker_id.to_string())).await;
+
+        Ok(())
+    }
+
+    pub async fn search_tasks(&self, workspace_id: Uuid, filter: &TaskFilter, phrase: &str, take: i32, continuation_token: &str) -> (Vec<TaskSearchDto>, String) {
+        let (entities, ct) = self.repo.search_tasks(workspace_id, filter, phrase, take, continuation_token).await;
+        (entities.iter().map(TaskSearchDto::new).collect(), ct)
+    }
+
+    // Walks up from a task that was just finished or reopened. Finishing completes every parent whose subtasks are all done,
+    // reopening reopens the done parents. Automatic moves still go through the workflow, a move it refuses stops the walk
+    async fn roll_up(&self, workspace_id: Uuid, task: &TaskEntity) -> Result<(), Error> {
+        let mut ancestors: Vec<TaskEntity> = vec![];
+        let mut parent_id = task.root_task_id;
+        while let Some(id) = parent_id {
+            let Ok(parent) = self.repo.get_by_id(workspace_id, id).await else { break };
+            parent_id = parent.root_task_id;
+            ancestors.push(parent);
+        }
+
+        if !ancestors.last().is_some_and(|root| root.auto_complete) {
+            return Ok(());
+        }
+
+        let finished = task.status.category == StatusCategory::Done;
+        let statuses = self.status_service.get_all_statuses(workspace_id).await;
+        let target = if finished {
+            statuses.iter().find(|s| s.category == StatusCategory::Done)
+        } else {
+            statuses.iter().find(|s| s.category == StatusCategory::InProgress).or(statuses.iter().find(|s| s.category == StatusCategory::Todo))
+        };
+
+        let Some(target) = target else { return Ok(()) };
+
+        for parent in ancestors {
+            let parent_done = parent.status.category == StatusCategory::Done;
+            if finished == parent_done {
+                break;
+            }
+
+            if finished && self.repo.get_subtasks(workspace_id, parent.id).await.iter().any(|s| s.status.category != StatusCategory::Done) {
+                break;
+            }
+
+            let filled_fields = filled_fields(parent.resolution.as_deref(), parent.description.as_deref(), parent.assignee.as_deref());
+            let Ok(hooks) = self.workflow_service.check_transition(workspace_id, &parent.status, target, &filled_fields).await else { break };
+
+            self.apply_rolled_up_status(workspace_id, &parent, target, hooks).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn apply_rolled_up_status(&self, workspace_id: Uuid, parent: &TaskEntity, status: &StatusEntity, hooks: Vec<StatusHook>) -> Result<(), Error> {
+        self.repo.update_status(workspace_id, parent.id, status.id).await?;
+
+        let payload = format!("{} -> {}", parent.status.name, status.name);
+        self.log_service.log_task_action(TaskAction::StatusRolledUp, Some(parent.id), Some("TaskEntity"), Some(&payload)).await;
+
+        for hook in hooks {
+            self.run_hook(workspace_id, parent.id, hook).await?;
+        }
+
+        let updated = self.repo.get_by_id(workspace_id, parent.id).await?;
+        self.notification_service.notify_status_changed(&updated, &parent.status).await?;
+
+        if status.category == StatusCategory::Done {
+            self.recurrence_service.generate_next(&updated).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_hook(&self, workspace_id: Uuid, task_id: Uuid, hook: StatusHook) -> Result<(), Error> {
+        match hook {
+            StatusHook::CompleteChecklist => {
+                for item in self.checklist_repo.get_by_task(task_id).await.iter().filter(|i| !i.done) {
+                    self.checklist_repo.set_done(task_id, item.id, true).await?;
+                    self.log_service.log_task_action(TaskAction::ChecklistItemToggled, Some(task_id), Some("TaskEntity"), Some(&item.id.to_string())).await;
+                }
+            },
+
+            StatusHook::ClearAssignee => {
+                self.repo.update_assignee(workspace_id, task_id, None).await?;
+                self.log_service.log_task_action(TaskAction::AssigneeChanged, Some(task_id), Some("TaskEntity"), None).await;
+            },
+
+            StatusHook::ClearResolution => {
+                self.repo.update_resolution(workspace_id, task_id, None).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_tags_by_task(&self, entities: &[TaskEntity]) -> HashMap<Uuid, Vec<TagEntity>> {
+        let ids: Vec<Uuid> = entities.iter().map(|e| e.id).collect();
+        let mut result: HashMap<Uuid, Vec<TagEntity>> = HashMap::new();
+
+        for (task_id, tag) in self.tag_repo.get_by_tasks(&ids).await {
+            result.entry(task_id).or_default().push(tag);
+        }
+
+        result
+    }
+}
+
+// Only the fields present in the patch become columns to write
+fn task_changes(patch: &TaskPatchDto, prepared: &PreparedUpdate) -> TaskChanges {
+    TaskChanges {
+        summary: patch.summary.clone(),
+        description: patch.description.clone(),
+        due_date: patch.due_date,
+        priority_id: patch.priority.as_ref().map(|_| prepared.priority.id),
+        status_id: patch.status.as_ref().map(|_| prepared.status.id),
+        resolution: patch.resolution.clone(),
+        expected_version: prepared.guard
+    }
+}
+
+// Bulk status and priority operations are plain patches of a single field
+fn status_patch(status: &str, resolution: Option<&str>) -> TaskPatchDto {
+    TaskPatchDto {
+        status: Some(status.to_string()),
+        resolution: resolution.map(|r| Some(r.to_string())),
+        ..Default::default()
+    }
+}
+
+fn priority_patch(priority: &str) -> TaskPatchDto {
+    TaskPatchDto { priority: Some(priority.to_string()), ..Default::default() }
+}
+
+// Fields a workflow transition can require that have a value
+fn filled_fields<'a>(resolution: Option<&str>, description: Option<&str>, assignee: Option<&str>) -> Vec<&'a str> {
+    [("resolution", resolution), ("description", description), ("assignee", assignee)]
+        .into_iter()
+        .filter(|(_, value)| value.is_some_and(|v| !v.trim().is_empty()))
+        .map(|(field, _)| field)
+        .collect()
+}
@@ -0,0 +1,209 @@
+use std::{sync::Arc, collections::HashMap};
+
+use domain::models::{TemplateEntity, TemplateNodeEntity};
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{dtos::{TemplateDto, TemplateSummaryDto, UpsertTemplateDto, TemplateNodeDto, InstantiateTemplateDto, UpsertTaskDto, TaskAction}, repos::{TemplateRepository, WorkspaceRepository}, errors::Error, logs::LogService, statuses::StatusService, tasks::TaskService};
+
+// Keeps a single template from turning into a bulk import
+const MAX_NODES: usize = 500;
+
+// Due dates can't be set further than that from the instantiation date
+const MAX_DUE_IN_DAYS: i32 = 3650;
+
+pub struct TemplateService {
+    repo: Arc<dyn TemplateRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    task_service: Arc<TaskService>,
+    status_service: Arc<StatusService>,
+    log_service: Arc<LogService>
+}
+
+impl TemplateService {
+    pub fn new(repo: Arc<dyn TemplateRepository>, workspace_repo: Arc<dyn WorkspaceRepository>, task_service: Arc<TaskService>, status_service: Arc<StatusService>, log_service: Arc<LogService>) -> TemplateService {
+        TemplateService { repo, workspace_repo, task_service, status_service, log_service }
+    }
+
+    pub async fn get_templates(&self, workspace_id: Uuid) -> Vec<TemplateSummaryDto> {
+        let entities = self.repo.get_all(workspace_id).await;
+
+        entities.iter().map(TemplateSummaryDto::new).collect()
+    }
+
+    pub async fn get_template(&self, workspace_id: Uuid, id: Uuid) -> Result<TemplateDto, Error> {
+        let entity = self.repo.get_by_id(workspace_id, id).await?;
+        let nodes = self.repo.get_nodes(id).await;
+
+        let mut variables = vec![];
+        for node in &nodes {
+            for name in placeholders(&node.summary).into_iter().chain(placeholders(node.description.as_deref().unwrap_or_default())) {
+                if !variables.contains(&name) {
+                    variables.push(name);
+                }
+            }
+        }
+
+        Ok(TemplateDto::new(&entity, variables, build_node(&nodes)))
+    }
+
+    pub async fn create_template(&self, workspace_id: Uuid, details: &UpsertTemplateDto) -> Result<Uuid, Error> {
+        self.workspace_repo.get_by_id(workspace_id).await?;
+
+        let id = Uuid::new_v4();
+        let nodes = self.flatten(workspace_id, id, details).await?;
+        let entity = TemplateEntity {
+            id,
+            workspace_id,
+            name: details.name.trim().to_string(),
+            create_date: Utc::now()
+        };
+
+        self.repo.insert(entity, nodes).await?;
+        self.log_service.log_task_action(TaskAction::Create, Some(id), Some("TemplateEntity"), None).await;
+
+        Ok(id)
+    }
+
+    pub async fn update_template(&self, workspace_id: Uuid, id: Uuid, details: &UpsertTemplateDto) -> Result<(), Error> {
+        self.repo.get_by_id(workspace_id, id).await?;
+
+        let nodes = self.flatten(workspace_id, id, details).await?;
+
+        self.repo.update(workspace_id, id, details.name.trim(), nodes).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(id), Some("TemplateEntity"), None).await;
+
+        Ok(())
+    }
+
+    pub async fn delete_template(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        self.repo.delete(workspace_id, id).await?;
+        self.log_service.log_task_action(TaskAction::Delete, Some(id), Some("TemplateEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Tasks start in the initial status of the workspace, the returned id is the one of the top task
+    pub async fn instantiate(&self, workspace_id: Uuid, id: Uuid, details: &InstantiateTemplateDto) -> Result<Uuid, Error> {
+        self.repo.get_by_id(workspace_id, id).await?;
+
+        let nodes = self.repo.get_nodes(id).await;
+        let status = self.status_service.initial_status(workspace_id).await?;
+        let date = details.date.unwrap_or(Utc::now());
+
+        let mut indexes: HashMap<Uuid, usize> = HashMap::new();
+        let mut items: Vec<(Option<usize>, UpsertTaskDto)> = vec![];
+        for node in &nodes {
+            let task = UpsertTaskDto {
+                summary: render(&node.summary, &details.variables)?,
+                priority: node.priority.clone(),
+                status: status.name.clone(),
+                // Tasks always have a description, possibly an empty one
+                description: Some(node.description.as_deref().map(|d| render(d, &details.variables)).transpose()?.unwrap_or_default()),
+                due_date: date + Duration::days(node.due_in_days as i64),
+                resolution: None
+            };
+
+            indexes.insert(node.id, items.len());
+            items.push((node.parent_id.and_then(|p| indexes.get(&p).copied()), task));
+        }
+
+        let ids = self.task_service.create_task_tree(workspace_id, details.root_id, &items).await?;
+
+        Ok(ids[0])
+    }
+
+    // Depth first, so every parent lands before its subtasks
+    async fn flatten(&self, workspace_id: Uuid, template_id: Uuid, details: &UpsertTemplateDto) -> Result<Vec<TemplateNodeEntity>, Error> {
+        if details.name.trim().is_empty() {
+            return Err(Error::invalid_input("Name can't be empty"));
+        }
+
+        let mut nodes: Vec<TemplateNodeEntity> = vec![];
+        let mut pending: Vec<(Option<Uuid>, &TemplateNodeDto)> = vec![(None, &details.root)];
+        while let Some((parent_id, node)) = pending.pop() {
+            if nodes.len() == MAX_NODES {
+                return Err(Error::TooLarge(format!("Template can't have more than {} tasks", MAX_NODES)));
+            }
+
+            if node.summary.trim().is_empty() {
+                return Err(Error::invalid_input("Summary can't be empty"));
+            }
+
+            if node.due_in_days.abs() > MAX_DUE_IN_DAYS {
+                return Err(Error::InvalidInput(format!("Due date can't be more than {} days away", MAX_DUE_IN_DAYS)));
+            }
+
+            let priority = self.status_service.resolve_priority(workspace_id, &node.priority).await?;
+
+            let id = Uuid::new_v4();
+            nodes.push(TemplateNodeEntity {
+                id,
+                template_id,
+                parent_id,
+                position: nodes.len() as i32,
+                summary: node.summary.clone(),
+                description: node.description.clone(),
+                priority: priority.name,
+                due_in_days: node.due_in_days
+            });
+
+            pending.extend(node.subtasks.iter().rev().map(|s| (Some(id), s)));
+        }
+
+        Ok(nodes)
+    }
+}
+
+fn build_node(nodes: &[TemplateNodeEntity]) -> TemplateNodeDto {
+    let mut children: HashMap<Option<Uuid>, Vec<&TemplateNodeEntity>> = HashMap::new();
+    for node in nodes {
+        children.entry(node.parent_id).or_default().push(node);
+    }
+
+    // Templates are stored with exactly one top node
+    to_dto(&nodes[0], &children)
+}
+
+fn to_dto(node: &TemplateNodeEntity, children: &HashMap<Option<Uuid>, Vec<&TemplateNodeEntity>>) -> TemplateNodeDto {
+    TemplateNodeDto {
+        summary: node.summary.clone(),
+        description: node.description.clone(),
+        priority: node.priority.clone(),
+        due_in_days: node.due_in_days,
+        subtasks: children.get(&Some(node.id)).map(|c| c.iter().map(|child| to_dto(child, children)).collect()).unwrap_or_default()
+    }
+}
+
+// Names of the {{variable}} placeholders in order of appearance, a name is letters, digits and underscores
+fn placeholders(text: &str) -> Vec<String> {
+    let mut result = vec![];
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        match rest.find("}}") {
+            Some(end) if is_variable_name(&rest[..end]) => {
+                result.push(rest[..end].to_string());
+                rest = &rest[end + 2..];
+            },
+            _ => continue
+        }
+    }
+
+    result
+}
+
+fn render(text: &str, variables: &HashMap<String, String>) -> Result<String, Error> {
+    let mut result = text.to_string();
+    for name in placeholders(text) {
+        let value = variables.get(&name).ok_or(Error::InvalidInput(format!("Missing value for variable {}", name)))?;
+        result = result.replace(&format!("{{{{{}}}}}", name), value);
+    }
+
+    Ok(result)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
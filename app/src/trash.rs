@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::{dtos::{TrashItemDto, RestoreSummaryDto, TaskAction}, repos::{TrashRepository, TaskRepository}, errors::Error, logs::LogService, attachments::AttachmentService};
+
+pub struct TrashService {
+    repo: Arc<dyn TrashRepository>,
+    task_repo: Arc<dyn TaskRepository>,
+    attachment_service: Arc<AttachmentService>,
+    log_service: Arc<LogService>,
+    retention_days: i64 // 0 turns the automatic purge off
+}
+
+impl TrashService {
+    pub fn new(repo: Arc<dyn TrashRepository>, task_repo: Arc<dyn TaskRepository>, attachment_service: Arc<AttachmentService>, log_service: Arc<LogService>, retention_days: i64) -> TrashService {
+        TrashService { repo, task_repo, attachment_service, log_service, retention_days }
+    }
+
+    pub async fn get_trash(&self, workspace_id: Uuid, take: i32, continuation_token: &str) -> (Vec<TrashItemDto>, String) {
+        let (entities, continuation_token) = self.repo.get_batch(workspace_id, take, continuation_token).await;
+
+        (entities.iter().map(TrashItemDto::new).collect(), continuation_token)
+    }
+
+    // Brings back the task with everything trashed below it. The task returns under its original parent,
+    // or becomes a root task when that parent is gone or still in the trash. Subtasks that were orphaned or reparented
+    // by the delete come back under it, unless they have been moved since or the task now sits below them
+    pub async fn restore(&self, workspace_id: Uuid, task_id: Uuid) -> Result<RestoreSummaryDto, Error> {
+        let item = self.repo.get_by_id(workspace_id, task_id).await?;
+
+        let root_id = match item.task.root_task_id {
+            Some(parent_id) => self.task_repo.get_by_id(workspace_id, parent_id).await.ok().map(|p| p.id),
+            None => None
+        };
+
+        let mut above = vec![];
+        if let Some(root_id) = root_id {
+            above = self.task_repo.get_ancestors(workspace_id, root_id).await.into_iter().map(|a| a.id).collect();
+            above.push(root_id);
+        }
+
+        let reattached: Vec<Uuid> = self.repo.get_detached(workspace_id, task_id).await
+            .into_iter()
+            .filter(|id| !above.contains(id))
+            .collect();
+
+        let ids = self.subtree(workspace_id, task_id).await;
+        self.repo.restore(workspace_id, &ids, root_id, &reattached).await?;
+
+        for id in &ids {
+            let payload = if *id == task_id { root_id.map(|r| r.to_string()) } else { None };
+            self.log_service.log_task_action(TaskAction::Restored, Some(*id), Some("TaskEntity"), payload.as_deref()).await;
+        }
+
+        for id in &reattached {
+            self.log_service.log_task_action(TaskAction::RootChanged, Some(*id), Some("TaskEntity"), Some(&task_id.to_string())).await;
+        }
+
+        Ok(RestoreSummaryDto::new(&ids, root_id, &reattached))
+    }
+
+    // Removes the task with everything trashed below it for good
+    pub async fn purge(&self, workspace_id: Uuid, task_id: Uuid) -> Result<Vec<Uuid>, Error> {
+        self.repo.get_by_id(workspace_id, task_id).await?;
+
+        let ids = self.subtree(workspace_id, task_id).await;
+
+        // Attachment rows go away with the task (ON DELETE CASCADE), files have to be released manually
+        let mut attachment_hashes = vec![];
+        for id in &ids {
+            attachment_hashes.extend(self.attachment_service.get_task_hashes(*id).await);
+        }
+
+        self.repo.delete_many(workspace_id, &ids).await?;
+        self.attachment_service.release_files(&attachment_hashes).await;
+
+        for id in &ids {
+            self.log_service.log_task_action(TaskAction::Purged, Some(*id), Some("TaskEntity"), None).await;
+        }
+
+        Ok(ids)
+    }
+
+    // One pass of the cleanup worker, returns the number of purged tasks
+    pub async fn purge_expired(&self, now: DateTime<Utc>) -> usize {
+        if self.retention_days <= 0 {
+            return 0;
+        }
+
+        let Some(deleted_before) = Duration::try_days(self.retention_days).and_then(|d| now.checked_sub_signed(d)) else {
+            return 0;
+        };
+
+        let mut purged = 0;
+        for item in self.repo.get_expired(deleted_before).await {
+            // Someone may have restored or purged it in the meantime, that is fine
+            if let Ok(ids) = self.purge(item.task.workspace_id, item.task.id).await {
+                purged += ids.len();
+            }
+        }
+
+        purged
+    }
+
+    // The task first, so the repo knows which one to reattach
+    async fn subtree(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<Uuid> {
+        let mut ids = vec![task_id];
+        ids.extend(self.repo.get_subtree(workspace_id, task_id).await.into_iter().filter(|id| *id != task_id));
+
+        ids
+    }
+}
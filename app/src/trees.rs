@@ -0,0 +1,97 @@
+use std::{sync::Arc, collections::{HashMap, HashSet}};
+
+use domain::models::TaskTreeNodeEntity;
+
+use uuid::Uuid;
+
+use crate::{dtos::{TaskTreeNodeDto, TaskTreeItemDto}, repos::TaskRepository, errors::Error, statuses::StatusService};
+
+pub struct TreeService {
+    repo: Arc<dyn TaskRepository>,
+    status_service: Arc<StatusService>
+}
+
+impl TreeService {
+    pub fn new(repo: Arc<dyn TaskRepository>, status_service: Arc<StatusService>) -> TreeService {
+        TreeService { repo, status_service }
+    }
+
+    pub async fn get_tree(&self, workspace_id: Uuid, task_id: Uuid, max_depth: Option<i32>, statuses: &[String]) -> Result<TaskTreeNodeDto, Error> {
+        let nodes = self.load(workspace_id, task_id, max_depth, statuses).await?;
+
+        let mut children: HashMap<Uuid, Vec<&TaskTreeNodeEntity>> = HashMap::new();
+        for node in nodes.iter().skip(1) {
+            if let Some(parent_id) = node.task.root_task_id {
+                children.entry(parent_id).or_default().push(node);
+            }
+        }
+
+        Ok(build_node(&nodes[0], &children))
+    }
+
+    // Same nodes as the nested form, depth first so the list reads as an outline
+    pub async fn get_flat_tree(&self, workspace_id: Uuid, task_id: Uuid, max_depth: Option<i32>, statuses: &[String]) -> Result<Vec<TaskTreeItemDto>, Error> {
+        let nodes = self.load(workspace_id, task_id, max_depth, statuses).await?;
+
+        let mut children: HashMap<Uuid, Vec<&TaskTreeNodeEntity>> = HashMap::new();
+        for node in nodes.iter().skip(1) {
+            if let Some(parent_id) = node.task.root_task_id {
+                children.entry(parent_id).or_default().push(node);
+            }
+        }
+
+        let mut result = vec![];
+        let mut pending = vec![&nodes[0]];
+        while let Some(node) = pending.pop() {
+            result.push(TaskTreeItemDto::new(node));
+            if let Some(node_children) = children.get(&node.task.id) {
+                pending.extend(node_children.iter().rev());
+            }
+        }
+
+        Ok(result)
+    }
+
+    // The task itself comes first. With a status filter a node stays when it or anything below it matches,
+    // so every match keeps its path to the top
+    async fn load(&self, workspace_id: Uuid, task_id: Uuid, max_depth: Option<i32>, statuses: &[String]) -> Result<Vec<TaskTreeNodeEntity>, Error> {
+        if max_depth.is_some_and(|d| d < 0) {
+            return Err(Error::invalid_input("Depth can't be negative"));
+        }
+
+        let mut status_ids = HashSet::new();
+        for name in statuses {
+            status_ids.insert(self.status_service.resolve_status(workspace_id, name).await?.id);
+        }
+
+        let nodes = self.repo.get_subtree(workspace_id, task_id, max_depth).await;
+        if nodes.is_empty() {
+            return Err(Error::not_found(task_id));
+        }
+
+        if status_ids.is_empty() {
+            return Ok(nodes);
+        }
+
+        // Nodes are ordered by depth, walking backwards reaches every child before its parent
+        let mut kept: HashSet<Uuid> = HashSet::from([task_id]);
+        for node in nodes.iter().rev() {
+            if status_ids.contains(&node.task.status.id) || kept.contains(&node.task.id) {
+                kept.insert(node.task.id);
+                if let Some(parent_id) = node.task.root_task_id {
+                    kept.insert(parent_id);
+                }
+            }
+        }
+
+        Ok(nodes.into_iter().filter(|n| kept.contains(&n.task.id)).collect())
+    }
+}
+
+fn build_node(node: &TaskTreeNodeEntity, children: &HashMap<Uuid, Vec<&TaskTreeNodeEntity>>) -> TaskTreeNodeDto {
+    let node_children = children.get(&node.task.id)
+        .map(|c| c.iter().map(|child| build_node(child, children)).collect())
+        .unwrap_or_default();
+
+    TaskTreeNodeDto::new(node, node_children)
+}
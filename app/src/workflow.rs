@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use domain::models::{WorkflowTransitionEntity, WorkflowHookEntity, StatusEntity};
+
+use uuid::Uuid;
+
+use crate::{dtos::{WorkflowDto, WorkflowTransitionDto, WorkflowHookDto, TaskAction}, repos::{WorkflowRepository, WorkspaceRepository}, errors::Error, logs::LogService, statuses::StatusService};
+
+// Fields a transition can insist on
+const REQUIRED_FIELDS: [&str; 3] = ["resolution", "description", "assignee"];
+
+// Side effects run by the task service when a task enters a status
+#[derive(Clone, Copy, PartialEq)]
+pub enum StatusHook {
+    CompleteChecklist,
+    ClearAssignee,
+    ClearResolution,
+}
+
+impl StatusHook {
+    pub fn parse(name: &str) -> Result<StatusHook, Error> {
+        match name {
+            "complete_checklist" => Ok(StatusHook::CompleteChecklist),
+            "clear_assignee" => Ok(StatusHook::ClearAssignee),
+            "clear_resolution" => Ok(StatusHook::ClearResolution),
+            _ => Err(Error::InvalidInput(format!("Unknown workflow hook: {}", name)))
+        }
+    }
+}
+
+pub struct WorkflowService {
+    repo: Arc<dyn WorkflowRepository>,
+    workspace_repo: Arc<dyn WorkspaceRepository>,
+    status_service: Arc<StatusService>,
+    log_service: Arc<LogService>
+}
+
+impl WorkflowService {
+    pub fn new(repo: Arc<dyn WorkflowRepository>, workspace_repo: Arc<dyn WorkspaceRepository>, status_service: Arc<StatusService>, log_service: Arc<LogService>) -> WorkflowService {
+        WorkflowService { repo, workspace_repo, status_service, log_service }
+    }
+
+    pub async fn get_workflow(&self, workspace_id: Uuid) -> Result<WorkflowDto, Error> {
+        self.workspace_repo.get_by_id(workspace_id).await?;
+
+        let statuses = self.status_service.get_all_statuses(workspace_id).await;
+        let name = |id: Uuid| statuses.iter().find(|s| s.id == id).map_or(String::new(), |s| s.name.clone());
+
+        let transitions = self.repo.get_transitions(workspace_id).await;
+        let hooks = self.repo.get_hooks(workspace_id).await;
+
+        Ok(WorkflowDto {
+            transitions: transitions.iter().map(|t| WorkflowTransitionDto { from: name(t.from_status_id), to: name(t.to_status_id), required_fields: t.required_fields.clone() }).collect(),
+            hooks: hooks.iter().map(|h| WorkflowHookDto { status: name(h.status_id), hook: h.hook.clone() }).collect()
+        })
+    }
+
+    // Replaces the whole workflow of the workspace
+    pub async fn set_workflow(&self, workspace_id: Uuid, details: &WorkflowDto) -> Result<(), Error> {
+        self.workspace_repo.get_by_id(workspace_id).await?;
+
+        let mut transitions: Vec<WorkflowTransitionEntity> = vec![];
+        for t in &details.transitions {
+            let from_status = self.status_service.resolve_status(workspace_id, &t.from).await?;
+            let to_status = self.status_service.resolve_status(workspace_id, &t.to).await?;
+
+            if from_status.id == to_status.id {
+                return Err(Error::invalid_input("Transition must change the status"));
+            }
+
+            if transitions.iter().any(|e| e.from_status_id == from_status.id && e.to_status_id == to_status.id) {
+                return Err(Error::InvalidInput(format!("Duplicate transition from {} to {}", from_status.name, to_status.name)));
+            }
+
+            if let Some(field) = t.required_fields.iter().find(|f| !REQUIRED_FIELDS.contains(&f.as_str())) {
+                return Err(Error::InvalidInput(format!("Field {} can't be required, expected one of: {}", field, REQUIRED_FIELDS.join(", "))));
+            }
+
+            transitions.push(WorkflowTransitionEntity { workspace_id, from_status_id: from_status.id, to_status_id: to_status.id, required_fields: t.required_fields.clone() });
+        }
+
+        let mut hooks: Vec<WorkflowHookEntity> = vec![];
+        for h in &details.hooks {
+            StatusHook::parse(&h.hook)?;
+
+            let status = self.status_service.resolve_status(workspace_id, &h.status).await?;
+            if !hooks.iter().any(|e| e.status_id == status.id && e.hook == h.hook) {
+                hooks.push(WorkflowHookEntity { workspace_id, status_id: status.id, hook: h.hook.clone() });
+            }
+        }
+
+        self.repo.replace(workspace_id, transitions, hooks).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(workspace_id), Some("WorkflowEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Checks the move against the workflow of the workspace and returns hooks of the target status.
+    // `filled_fields` are the required-able fields that will have a value once the task is updated
+    pub async fn check_transition(&self, workspace_id: Uuid, from: &StatusEntity, to: &StatusEntity, filled_fields: &[&str]) -> Result<Vec<StatusHook>, Error> {
+        if from.id == to.id {
+            return Ok(vec![]);
+        }
+
+        let transitions = self.repo.get_transitions(workspace_id).await;
+
+        if !transitions.is_empty() {
+            let transition = match transitions.iter().find(|t| t.from_status_id == from.id && t.to_status_id == to.id) {
+                Some(t) => t,
+                None => {
+                    let statuses = self.status_service.get_all_statuses(workspace_id).await;
+                    let allowed: Vec<&str> = statuses.iter()
+                        .filter(|s| transitions.iter().any(|t| t.from_status_id == from.id && t.to_status_id == s.id))
+                        .map(|s| s.name.as_str())
+                        .collect();
+                    let allowed = if allowed.is_empty() { "none".to_string() } else { allowed.join(", ") };
+
+                    return Err(Error::InvalidTransition(format!("Transition from {} to {} is not allowed, allowed targets: {}", from.name, to.name, allowed)));
+                }
+            };
+
+            let missing: Vec<&str> = transition.required_fields.iter().map(|f| f.as_str()).filter(|f| !filled_fields.contains(f)).collect();
+            if !missing.is_empty() {
+                return Err(Error::InvalidTransition(format!("Transition from {} to {} requires: {}", from.name, to.name, missing.join(", "))));
+            }
+        }
+
+        self.repo.get_hooks(workspace_id).await
+            .iter()
+            .filter(|h| h.status_id == to.id)
+            .map(|h| StatusHook::parse(&h.hook))
+            .collect()
+    }
+}
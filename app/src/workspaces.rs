@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use domain::models::WorkspaceEntity;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{dtos::{WorkspaceDto, UpsertWorkspaceDto, TaskAction}, repos::WorkspaceRepository, errors::Error, logs::LogService, statuses::StatusService, attachments::AttachmentService};
+
+pub struct WorkspaceService {
+    repo: Arc<dyn WorkspaceRepository>,
+    status_service: Arc<StatusService>,
+    attachment_service: Arc<AttachmentService>,
+    log_service: Arc<LogService>
+}
+
+impl WorkspaceService {
+    pub fn new(repo: Arc<dyn WorkspaceRepository>, status_service: Arc<StatusService>, attachment_service: Arc<AttachmentService>, log_service: Arc<LogService>) -> WorkspaceService {
+        WorkspaceService { repo, status_service, attachment_service, log_service }
+    }
+
+    pub async fn get_workspaces(&self) -> Vec<WorkspaceDto> {
+        let entities = self.repo.get_all().await;
+
+        entities.iter().map(WorkspaceDto::new).collect()
+    }
+
+    pub async fn get_workspace(&self, id: Uuid) -> Result<WorkspaceDto, Error> {
+        let entity = self.repo.get_by_id(id).await?;
+
+        Ok(WorkspaceDto::new(&entity))
+    }
+
+    pub async fn create_workspace(&self, details: &UpsertWorkspaceDto) -> Result<Uuid, Error> {
+        let id = Uuid::new_v4();
+        let entity = WorkspaceEntity {
+            id,
+            name: details.name.clone(),
+            create_date: Utc::now()
+        };
+
+        let (statuses, priorities) = self.status_service.defaults(id);
+
+        self.repo.insert(entity, statuses, priorities).await?;
+        self.log_service.log_task_action(TaskAction::Create, Some(id), Some("WorkspaceEntity"), None).await;
+
+        Ok(id)
+    }
+
+    pub async fn update_workspace(&self, id: Uuid, details: &UpsertWorkspaceDto) -> Result<(), Error> {
+        self.repo.update(id, details.name.as_str()).await?;
+        self.log_service.log_task_action(TaskAction::Update, Some(id), Some("WorkspaceEntity"), None).await;
+
+        Ok(())
+    }
+
+    // Tasks of the workspace are removed by the database (ON DELETE CASCADE), their files are released afterwards
+    pub async fn delete_workspace(&self, id: Uuid) -> Result<(), Error> {
+        let attachment_hashes = self.attachment_service.get_workspace_hashes(id).await;
+
+        self.repo.delete(id).await?;
+        self.attachment_service.release_files(&attachment_hashes).await;
+        self.log_service.log_task_action(TaskAction::Delete, Some(id), Some("WorkspaceEntity"), None).await;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-const BASE_URL = 'http://localhost:3005/api/tasks';
+const API_URL = 'http://localhost:3005/api';
+const WORKSPACE_ID = '00000000-0000-0000-0000-000000000001';
+const BASE_URL = `${API_URL}/workspaces/${WORKSPACE_ID}/tasks`;
 
 async function getErrorContent(response) {
     try {
@@ -29,7 +31,7 @@ export async function getTasks(take, continuationToken, orderBy, descendingSort)
 }
 
 export async function getLogs(take, continuationToken, descending) {
-    const url = `${BASE_URL}/logs?take=${take}&continuation_token=${continuationToken}&descending=${descending}`;
+    const url = `${API_URL}/tasks/logs?take=${take}&continuation_token=${continuationToken}&descending=${descending}`;
     var response = await fetch(url);
 
     if (!response.ok) {
@@ -1,15 +1,9 @@
-pub enum TaskPriority {
-    Low,
-    Normal,
-    High,
-    Urgent,
-}
-
-pub enum TaskStatus {
-    Reserved,
-    Ongoing,
+// What a workspace defined status means for the rest of the system, e.g. only done tasks unblock others
+#[derive(Clone, Copy, PartialEq)]
+pub enum StatusCategory {
+    Todo,
+    InProgress,
     Done,
-    Pending,
 }
 
 pub enum TaskAction {
@@ -17,4 +11,30 @@ pub enum TaskAction {
     Delete,
     Update,
     RootChanged,
+    TagAdded,
+    TagRemoved,
+    CommentAdded,
+    CommentEdited,
+    CommentDeleted,
+    AttachmentAdded,
+    AttachmentRemoved,
+    ChecklistItemAdded,
+    ChecklistItemToggled,
+    ChecklistItemRemoved,
+    ChecklistReordered,
+    DependencyAdded,
+    DependencyRemoved,
+    DueDateAdjusted,
+    RecurrenceChanged,
+    RecurrenceGenerated,
+    ReminderFired,
+    AssigneeChanged,
+    StatusChanged,
+    StatusRolledUp,
+    Restored,
+    Purged,
+    Archived,
+    Unarchived,
+    Reordered,
+    ReminderFailed,
 }
\ No newline at end of file
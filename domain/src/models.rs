@@ -3,15 +3,171 @@ use uuid::Uuid;
 
 use crate::enums;
 
+pub struct WorkspaceEntity {
+    pub id: Uuid,
+    pub name: String,
+    pub create_date: DateTime<Utc>,
+}
+
 pub struct TaskEntity {
     pub id: Uuid,
+    pub workspace_id: Uuid,
     pub root_task_id: Option<Uuid>,
     pub summary: String,
     pub description: Option<String>,
     pub create_date: DateTime<Utc>,
     pub due_date: DateTime<Utc>,
-    pub priority: enums::TaskPriority,
-    pub status: enums::TaskStatus,
+    pub assignee: Option<String>, // email of the person the task is assigned to
+    pub resolution: Option<String>, // note left when the task was finished
+    pub auto_complete: bool, // read from the root task only, parents follow the state of their subtasks
+    pub archived_at: Option<DateTime<Utc>>,
+    pub rank: String, // position among the siblings, compared byte by byte
+    pub version: i32, // bumped by every update of the row, exposed as the ETag
+    pub priority: PriorityEntity,
+    pub status: StatusEntity,
+}
+
+// Statuses and priorities are defined per workspace and loaded along with every task
+#[derive(Clone)]
+pub struct StatusEntity {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub position: i32, // board ordering
+    pub category: enums::StatusCategory,
+}
+
+#[derive(Clone)]
+pub struct PriorityEntity {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub position: i32, // higher is more important
+}
+
+pub struct TagEntity {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub color: String,
+}
+
+pub struct CommentEntity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author: Option<String>,
+    pub body: String,
+    pub create_date: DateTime<Utc>,
+    pub update_date: Option<DateTime<Utc>>,
+}
+
+// Previous body of an edited comment
+pub struct CommentRevisionEntity {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub body: String,
+    pub create_date: DateTime<Utc>,
+}
+
+pub struct AttachmentEntity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub hash: String, // sha256 of the content, files with equal content are stored once
+    pub create_date: DateTime<Utc>,
+}
+
+pub struct ChecklistItemEntity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub text: String,
+    pub done: bool,
+    pub position: i32,
+}
+
+pub struct RecurrenceEntity {
+    pub task_id: Uuid,
+    pub rule: String, // RRULE subset, e.g. FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;COUNT=10
+    pub clone_subtasks: bool,
+    pub occurrence: i32, // 1-based index of the task within its series
+    pub previous_task_id: Option<Uuid>,
+    pub next_task_id: Option<Uuid>,
+}
+
+pub struct ReminderEntity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub kind: String, // "overdue" or "before:<minutes>"
+    pub due_date: DateTime<Utc>, // due date the reminder was fired for, a moved task gets reminded again
+    pub fire_date: DateTime<Utc>,
+}
+
+pub struct NotificationPreferenceEntity {
+    pub email: String,
+    pub assignment: bool,
+    pub status_change: bool,
+    pub comments: bool,
+    pub due_soon: bool,
+    pub digest: bool, // collect notifications into one daily email instead of sending each
+}
+
+pub struct OutboxMessageEntity {
+    pub id: Uuid,
+    pub recipient: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+    pub digest: bool, // waits for the daily digest instead of being sent on its own
+    pub create_date: DateTime<Utc>,
+    pub attempts: i32,
+    pub next_attempt_date: DateTime<Utc>,
+    pub sent_date: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+// Empty set of transitions for a workspace means that any status can follow any other
+pub struct WorkflowTransitionEntity {
+    pub workspace_id: Uuid,
+    pub from_status_id: Uuid,
+    pub to_status_id: Uuid,
+    pub required_fields: Vec<String>, // have to be filled in before the task can move
+}
+
+pub struct WorkflowHookEntity {
+    pub workspace_id: Uuid,
+    pub status_id: Uuid,
+    pub hook: String, // runs when a task enters the status
+}
+
+// A task within a loaded subtree, `depth` is 0 for the task the subtree was loaded for
+pub struct TaskTreeNodeEntity {
+    pub task: TaskEntity,
+    pub depth: i32,
+    pub child_count: i64, // direct subtasks, the ones beyond the depth limit included
+}
+
+// A task in the trash, `subtask_count` is everything trashed below it
+pub struct TrashItemEntity {
+    pub task: TaskEntity,
+    pub deleted_at: DateTime<Utc>,
+    pub deleted_by: Option<String>,
+    pub subtask_count: i64,
+}
+
+// A request made with an `Idempotency-Key`, the response is filled in once the request has been handled
+pub struct IdempotencyKeyEntity {
+    pub workspace_id: Uuid, // nil for requests outside of any workspace
+    pub key: String,
+    pub fingerprint: String, // sha256 of the method, the path and the body
+    pub status_code: Option<i16>, // none while the first request is still running
+    pub content_type: Option<String>,
+    pub body: Option<Vec<u8>>,
+    pub create_date: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 pub struct TaskSearchEntity {
@@ -27,4 +183,23 @@ pub struct LogEntity {
     pub entity_id: Option<Uuid>,
     pub entity_type: Option<String>,
     pub payload: Option<String>
-}
\ No newline at end of file
+}
+
+pub struct TemplateEntity {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub create_date: DateTime<Utc>,
+}
+
+// Summary and description may hold {{variable}} placeholders, the due date is counted from the instantiation date
+pub struct TemplateNodeEntity {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub parent_id: Option<Uuid>, // none for the top node
+    pub position: i32,
+    pub summary: String,
+    pub description: Option<String>,
+    pub priority: String, // name of a workspace priority
+    pub due_in_days: i32,
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use app::{repos::{ReminderChannel, Mailer}, dtos::{ReminderDto, TaskAction}, errors::Error, logs::LogService};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+// The action log is the change feed clients already poll
+pub struct FeedChannel {
+    log_service: Arc<LogService>
+}
+
+pub struct WebhookChannel {
+    client: reqwest::Client,
+    url: String
+}
+
+// Sends every reminder to one fixed address, personal notices go through the notification service
+pub struct EmailChannel {
+    mailer: Arc<dyn Mailer>,
+    to: String
+}
+
+impl FeedChannel {
+    pub fn new(log_service: Arc<LogService>) -> FeedChannel {
+        FeedChannel { log_service }
+    }
+}
+
+impl WebhookChannel {
+    pub fn new(url: &str) -> WebhookChannel {
+        WebhookChannel { client: reqwest::Client::new(), url: url.to_string() }
+    }
+}
+
+impl EmailChannel {
+    pub fn new(mailer: Arc<dyn Mailer>, to: &str) -> EmailChannel {
+        EmailChannel { mailer, to: to.to_string() }
+    }
+}
+
+#[async_trait]
+impl ReminderChannel for FeedChannel {
+    fn name(&self) -> &str {
+        "feed"
+    }
+
+    async fn deliver(&self, reminder: &ReminderDto) -> Result<(), Error> {
+        let task_id = Uuid::parse_str(&reminder.task_id).ok();
+        self.log_service.log_task_action(TaskAction::ReminderFired, task_id, Some("TaskEntity"), Some(&reminder.kind)).await;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReminderChannel for WebhookChannel {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn deliver(&self, reminder: &ReminderDto) -> Result<(), Error> {
+        self.client
+            .post(&self.url)
+            .json(reminder)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReminderChannel for EmailChannel {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn deliver(&self, reminder: &ReminderDto) -> Result<(), Error> {
+        let subject = if reminder.kind == "overdue" {
+            format!("Overdue: {}", reminder.summary)
+        } else {
+            format!("Due soon: {}", reminder.summary)
+        };
+
+        let body = format!("Task \"{}\" ({}) is due {}.\n", reminder.summary, reminder.task_id, reminder.due_date.to_rfc2822());
+
+        self.mailer.send(&self.to, &subject, &body, None).await
+    }
+}
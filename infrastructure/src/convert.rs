@@ -1,17 +1,202 @@
-use domain::{enums::{TaskAction, TaskPriority, TaskStatus}, models::{TaskEntity, TaskSearchEntity, LogEntity}};
+use domain::{enums::{TaskAction, StatusCategory}, models::{TaskEntity, StatusEntity, PriorityEntity, TaskSearchEntity, LogEntity, WorkspaceEntity, TagEntity, CommentEntity, CommentRevisionEntity, AttachmentEntity, ChecklistItemEntity, RecurrenceEntity, ReminderEntity, NotificationPreferenceEntity, OutboxMessageEntity, WorkflowTransitionEntity, WorkflowHookEntity, TaskTreeNodeEntity, TemplateEntity, TemplateNodeEntity, TrashItemEntity, IdempotencyKeyEntity}};
 
 use sqlx::{postgres::PgRow, Row};
 
+pub fn row_to_workspace_entity(row: &PgRow) -> WorkspaceEntity {
+    WorkspaceEntity {
+        id: row.get("id"),
+        name: row.get("name"),
+        create_date: row.get("createdate"),
+    }
+}
+
+// Expects the status and priority columns joined in by `TASK_SELECT`
 pub fn row_to_task_entity(row: &PgRow) -> TaskEntity {
+    let workspace_id = row.get("workspaceid");
+
     TaskEntity {
         id: row.get("id"),
+        workspace_id,
         root_task_id: row.get("roottaskid"),
         summary: row.get("summary"),
         description: row.get("description"),
         create_date: row.get("createdate"),
         due_date: row.get("duedate"),
-        priority: priority_from_i16(row.get("priority")),
-        status: status_from_i16(row.get("status")),
+        assignee: row.get("assignee"),
+        resolution: row.get("resolution"),
+        auto_complete: row.get("autocomplete"),
+        archived_at: row.get("archivedat"),
+        rank: row.get("rank"),
+        version: row.get("version"),
+        priority: PriorityEntity {
+            id: row.get("priorityid"),
+            workspace_id,
+            name: row.get("priorityname"),
+            color: row.get("prioritycolor"),
+            position: row.get("priority"),
+        },
+        status: StatusEntity {
+            id: row.get("statusid"),
+            workspace_id,
+            name: row.get("statusname"),
+            color: row.get("statuscolor"),
+            position: row.get("status"),
+            category: category_from_i16(row.get("statuscategory")),
+        },
+    }
+}
+
+pub fn row_to_task_tree_node_entity(row: &PgRow) -> TaskTreeNodeEntity {
+    TaskTreeNodeEntity {
+        task: row_to_task_entity(row),
+        depth: row.get("depth"),
+        child_count: row.get("childcount"),
+    }
+}
+
+pub fn row_to_trash_item_entity(row: &PgRow) -> TrashItemEntity {
+    TrashItemEntity {
+        task: row_to_task_entity(row),
+        deleted_at: row.get("deletedat"),
+        deleted_by: row.get("deletedby"),
+        subtask_count: row.get("subtaskcount"),
+    }
+}
+
+pub fn row_to_status_entity(row: &PgRow) -> StatusEntity {
+    StatusEntity {
+        id: row.get("id"),
+        workspace_id: row.get("workspaceid"),
+        name: row.get("name"),
+        color: row.get("color"),
+        position: row.get("position"),
+        category: category_from_i16(row.get("category")),
+    }
+}
+
+pub fn row_to_priority_entity(row: &PgRow) -> PriorityEntity {
+    PriorityEntity {
+        id: row.get("id"),
+        workspace_id: row.get("workspaceid"),
+        name: row.get("name"),
+        color: row.get("color"),
+        position: row.get("position"),
+    }
+}
+
+pub fn row_to_tag_entity(row: &PgRow) -> TagEntity {
+    TagEntity {
+        id: row.get("id"),
+        workspace_id: row.get("workspaceid"),
+        name: row.get("name"),
+        color: row.get("color"),
+    }
+}
+
+pub fn row_to_comment_entity(row: &PgRow) -> CommentEntity {
+    CommentEntity {
+        id: row.get("id"),
+        task_id: row.get("taskid"),
+        author: row.get("author"),
+        body: row.get("body"),
+        create_date: row.get("createdate"),
+        update_date: row.get("updatedate"),
+    }
+}
+
+pub fn row_to_comment_revision_entity(row: &PgRow) -> CommentRevisionEntity {
+    CommentRevisionEntity {
+        id: row.get("id"),
+        comment_id: row.get("commentid"),
+        body: row.get("body"),
+        create_date: row.get("createdate"),
+    }
+}
+
+pub fn row_to_attachment_entity(row: &PgRow) -> AttachmentEntity {
+    AttachmentEntity {
+        id: row.get("id"),
+        task_id: row.get("taskid"),
+        file_name: row.get("filename"),
+        content_type: row.get("contenttype"),
+        size: row.get("size"),
+        hash: row.get("hash"),
+        create_date: row.get("createdate"),
+    }
+}
+
+pub fn row_to_checklist_item_entity(row: &PgRow) -> ChecklistItemEntity {
+    ChecklistItemEntity {
+        id: row.get("id"),
+        task_id: row.get("taskid"),
+        text: row.get("text"),
+        done: row.get("done"),
+        position: row.get("position"),
+    }
+}
+
+pub fn row_to_recurrence_entity(row: &PgRow) -> RecurrenceEntity {
+    RecurrenceEntity {
+        task_id: row.get("taskid"),
+        rule: row.get("rule"),
+        clone_subtasks: row.get("clonesubtasks"),
+        occurrence: row.get("occurrence"),
+        previous_task_id: row.get("previoustaskid"),
+        next_task_id: row.get("nexttaskid"),
+    }
+}
+
+pub fn row_to_reminder_entity(row: &PgRow) -> ReminderEntity {
+    ReminderEntity {
+        id: row.get("id"),
+        task_id: row.get("taskid"),
+        kind: row.get("kind"),
+        due_date: row.get("duedate"),
+        fire_date: row.get("firedate"),
+    }
+}
+
+pub fn row_to_notification_preference_entity(row: &PgRow) -> NotificationPreferenceEntity {
+    NotificationPreferenceEntity {
+        email: row.get("email"),
+        assignment: row.get("assignment"),
+        status_change: row.get("statuschange"),
+        comments: row.get("comments"),
+        due_soon: row.get("duesoon"),
+        digest: row.get("digest"),
+    }
+}
+
+pub fn row_to_outbox_message_entity(row: &PgRow) -> OutboxMessageEntity {
+    OutboxMessageEntity {
+        id: row.get("id"),
+        recipient: row.get("recipient"),
+        subject: row.get("subject"),
+        text_body: row.get("textbody"),
+        html_body: row.get("htmlbody"),
+        digest: row.get("digest"),
+        create_date: row.get("createdate"),
+        attempts: row.get("attempts"),
+        next_attempt_date: row.get("nextattemptdate"),
+        sent_date: row.get("sentdate"),
+        last_error: row.get("lasterror"),
+    }
+}
+
+pub fn row_to_workflow_transition_entity(row: &PgRow) -> WorkflowTransitionEntity {
+    WorkflowTransitionEntity {
+        workspace_id: row.get("workspaceid"),
+        from_status_id: row.get("fromstatusid"),
+        to_status_id: row.get("tostatusid"),
+        required_fields: row.get("requiredfields"),
+    }
+}
+
+pub fn row_to_workflow_hook_entity(row: &PgRow) -> WorkflowHookEntity {
+    WorkflowHookEntity {
+        workspace_id: row.get("workspaceid"),
+        status_id: row.get("statusid"),
+        hook: row.get("hook"),
     }
 }
 
@@ -40,6 +225,32 @@ pub fn action_from_i16(u: i16) -> TaskAction {
         1 => TaskAction::Delete,
         2 => TaskAction::Update,
         3 => TaskAction::RootChanged,
+        4 => TaskAction::TagAdded,
+        5 => TaskAction::TagRemoved,
+        6 => TaskAction::CommentAdded,
+        7 => TaskAction::CommentEdited,
+        8 => TaskAction::CommentDeleted,
+        9 => TaskAction::AttachmentAdded,
+        10 => TaskAction::AttachmentRemoved,
+        11 => TaskAction::ChecklistItemAdded,
+        12 => TaskAction::ChecklistItemToggled,
+        13 => TaskAction::ChecklistItemRemoved,
+        14 => TaskAction::ChecklistReordered,
+        15 => TaskAction::DependencyAdded,
+        16 => TaskAction::DependencyRemoved,
+        17 => TaskAction::DueDateAdjusted,
+        18 => TaskAction::RecurrenceChanged,
+        19 => TaskAction::RecurrenceGenerated,
+        20 => TaskAction::ReminderFired,
+        21 => TaskAction::AssigneeChanged,
+        22 => TaskAction::StatusChanged,
+        23 => TaskAction::StatusRolledUp,
+        24 => TaskAction::Restored,
+        25 => TaskAction::Purged,
+        26 => TaskAction::Archived,
+        27 => TaskAction::Unarchived,
+        28 => TaskAction::Reordered,
+        29 => TaskAction::ReminderFailed,
         _ => panic!("Invalid value of TaskAction")
     }
 }
@@ -49,44 +260,84 @@ pub fn action_to_i16(a: TaskAction) -> i16 {
         TaskAction::Create => 0,
         TaskAction::Delete => 1,
         TaskAction::Update => 2,
-        TaskAction::RootChanged => 3
+        TaskAction::RootChanged => 3,
+        TaskAction::TagAdded => 4,
+        TaskAction::TagRemoved => 5,
+        TaskAction::CommentAdded => 6,
+        TaskAction::CommentEdited => 7,
+        TaskAction::CommentDeleted => 8,
+        TaskAction::AttachmentAdded => 9,
+        TaskAction::AttachmentRemoved => 10,
+        TaskAction::ChecklistItemAdded => 11,
+        TaskAction::ChecklistItemToggled => 12,
+        TaskAction::ChecklistItemRemoved => 13,
+        TaskAction::ChecklistReordered => 14,
+        TaskAction::DependencyAdded => 15,
+        TaskAction::DependencyRemoved => 16,
+        TaskAction::DueDateAdjusted => 17,
+        TaskAction::RecurrenceChanged => 18,
+        TaskAction::RecurrenceGenerated => 19,
+        TaskAction::ReminderFired => 20,
+        TaskAction::AssigneeChanged => 21,
+        TaskAction::StatusChanged => 22,
+        TaskAction::StatusRolledUp => 23,
+        TaskAction::Restored => 24,
+        TaskAction::Purged => 25,
+        TaskAction::Archived => 26,
+        TaskAction::Unarchived => 27,
+        TaskAction::Reordered => 28,
+        TaskAction::ReminderFailed => 29
     }
 }
 
-pub fn priority_from_i16(u: i16) -> TaskPriority {
+pub fn category_from_i16(u: i16) -> StatusCategory {
     match u {
-        0 => TaskPriority::Low,
-        1 => TaskPriority::Normal,
-        2 => TaskPriority::High,
-        3 => TaskPriority::Urgent,
-        _ => panic!("Invalid value of TaskPriority")
+        0 => StatusCategory::Todo,
+        1 => StatusCategory::InProgress,
+        2 => StatusCategory::Done,
+        _ => panic!("Invalid value of StatusCategory")
     }
 }
 
-pub fn priority_to_i16(a: TaskPriority) -> i16 {
+pub fn category_to_i16(a: StatusCategory) -> i16 {
     match a {
-        TaskPriority::Low => 0,
-        TaskPriority::Normal => 1,
-        TaskPriority::High => 2,
-        TaskPriority::Urgent => 3
+        StatusCategory::Todo => 0,
+        StatusCategory::InProgress => 1,
+        StatusCategory::Done => 2
     }
 }
 
-pub fn status_from_i16(u: i16) -> TaskStatus {
-    match u {
-        0 => TaskStatus::Reserved,
-        1 => TaskStatus::Ongoing,
-        2 => TaskStatus::Done,
-        3 => TaskStatus::Pending,
-        _ => panic!("Invalid value of TaskStatus")
+pub fn row_to_template_entity(row: &PgRow) -> TemplateEntity {
+    TemplateEntity {
+        id: row.get("id"),
+        workspace_id: row.get("workspaceid"),
+        name: row.get("name"),
+        create_date: row.get("createdate"),
     }
 }
 
-pub fn status_to_i16(a: TaskStatus) -> i16 {
-    match a {
-        TaskStatus::Reserved => 0,
-        TaskStatus::Ongoing => 1,
-        TaskStatus::Done => 2,
-        TaskStatus::Pending => 3,
+pub fn row_to_template_node_entity(row: &PgRow) -> TemplateNodeEntity {
+    TemplateNodeEntity {
+        id: row.get("id"),
+        template_id: row.get("templateid"),
+        parent_id: row.get("parentid"),
+        position: row.get("position"),
+        summary: row.get("summary"),
+        description: row.get("description"),
+        priority: row.get("priority"),
+        due_in_days: row.get("dueindays"),
     }
-}
\ No newline at end of file
+}
+
+pub fn row_to_idempotency_key_entity(row: &PgRow) -> IdempotencyKeyEntity {
+    IdempotencyKeyEntity {
+        workspace_id: row.get("workspaceid"),
+        key: row.get("key"),
+        fingerprint: row.get("fingerprint"),
+        status_code: row.get("statuscode"),
+        content_type: row.get("contenttype"),
+        body: row.get("body"),
+        create_date: row.get("createdate"),
+        expires_at: row.get("expiresat"),
+    }
+}
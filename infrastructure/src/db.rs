@@ -1,9 +1,9 @@
-use app::{repos::{TaskRepository, LogRepository}, errors::Error};
-use domain::models::{TaskEntity, LogEntity};
+use app::{repos::{TaskRepository, LogRepository, WorkspaceRepository, TagRepository, CommentRepository, AttachmentRepository, ChecklistRepository, DependencyRepository, RecurrenceRepository, ReminderRepository, NotificationRepository, WorkflowRepository, StatusRepository, PriorityRepository, TemplateRepository, TrashRepository, IdempotencyRepository, TaskFilter, TaskChanges, TaskWrite, HashLock}, errors::Error};
+use domain::models::{TaskEntity, LogEntity, WorkspaceEntity, TagEntity, CommentEntity, CommentRevisionEntity, AttachmentEntity, ChecklistItemEntity, RecurrenceEntity, ReminderEntity, NotificationPreferenceEntity, OutboxMessageEntity, WorkflowTransitionEntity, WorkflowHookEntity, StatusEntity, PriorityEntity, TaskTreeNodeEntity, TemplateEntity, TemplateNodeEntity, TrashItemEntity, IdempotencyKeyEntity};
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{postgres::{PgPool, PgRow}, Row};
+use sqlx::{postgres::{PgConnection, PgPool, PgRow, Postgres}, Row, Transaction};
 use uuid::Uuid;
 
 use crate::convert;
@@ -18,6 +18,66 @@ pub struct LogStorage {
     pool: PgPool
 }
 
+pub struct WorkspaceStorage {
+    pool: PgPool
+}
+
+pub struct TagStorage {
+    pool: PgPool
+}
+
+pub struct StatusStorage {
+    pool: PgPool
+}
+
+pub struct PriorityStorage {
+    pool: PgPool
+}
+
+pub struct CommentStorage {
+    pool: PgPool
+}
+
+pub struct AttachmentStorage {
+    pool: PgPool
+}
+
+pub struct ChecklistStorage {
+    pool: PgPool
+}
+
+pub struct DependencyStorage {
+    pool: PgPool
+}
+
+pub struct RecurrenceStorage {
+    pool: PgPool
+}
+
+pub struct ReminderStorage {
+    pool: PgPool
+}
+
+pub struct NotificationStorage {
+    pool: PgPool
+}
+
+pub struct WorkflowStorage {
+    pool: PgPool
+}
+
+pub struct TemplateStorage {
+    pool: PgPool
+}
+
+pub struct TrashStorage {
+    pool: PgPool
+}
+
+pub struct IdempotencyStorage {
+    pool: PgPool
+}
+
 impl TaskStorage {
     pub fn new(pool: PgPool) -> TaskStorage {
         TaskStorage { pool }
@@ -30,12 +90,486 @@ impl LogStorage {
     }
 }
 
+impl WorkspaceStorage {
+    pub fn new(pool: PgPool) -> WorkspaceStorage {
+        WorkspaceStorage { pool }
+    }
+}
+
+impl TagStorage {
+    pub fn new(pool: PgPool) -> TagStorage {
+        TagStorage { pool }
+    }
+}
+
+impl StatusStorage {
+    pub fn new(pool: PgPool) -> StatusStorage {
+        StatusStorage { pool }
+    }
+}
+
+impl PriorityStorage {
+    pub fn new(pool: PgPool) -> PriorityStorage {
+        PriorityStorage { pool }
+    }
+}
+
+impl CommentStorage {
+    pub fn new(pool: PgPool) -> CommentStorage {
+        CommentStorage { pool }
+    }
+}
+
+impl AttachmentStorage {
+    pub fn new(pool: PgPool) -> AttachmentStorage {
+        AttachmentStorage { pool }
+    }
+}
+
+impl ChecklistStorage {
+    pub fn new(pool: PgPool) -> ChecklistStorage {
+        ChecklistStorage { pool }
+    }
+}
+
+impl DependencyStorage {
+    pub fn new(pool: PgPool) -> DependencyStorage {
+        DependencyStorage { pool }
+    }
+}
+
+impl RecurrenceStorage {
+    pub fn new(pool: PgPool) -> RecurrenceStorage {
+        RecurrenceStorage { pool }
+    }
+}
+
+impl ReminderStorage {
+    pub fn new(pool: PgPool) -> ReminderStorage {
+        ReminderStorage { pool }
+    }
+}
+
+impl NotificationStorage {
+    pub fn new(pool: PgPool) -> NotificationStorage {
+        NotificationStorage { pool }
+    }
+}
+
+impl WorkflowStorage {
+    pub fn new(pool: PgPool) -> WorkflowStorage {
+        WorkflowStorage { pool }
+    }
+}
+
+impl TemplateStorage {
+    pub fn new(pool: PgPool) -> TemplateStorage {
+        TemplateStorage { pool }
+    }
+}
+
+impl TrashStorage {
+    pub fn new(pool: PgPool) -> TrashStorage {
+        TrashStorage { pool }
+    }
+}
+
+impl IdempotencyStorage {
+    pub fn new(pool: PgPool) -> IdempotencyStorage {
+        IdempotencyStorage { pool }
+    }
+}
+
+// Every task below $1 (task id) within $2 (workspace id), the caller appends its own select over `cte`. Trashed tasks are skipped
+const SUBTASKS_CTE: &str = "with recursive cte (Id, RootTaskId) as ( \
+select     Id, \
+            RootTaskId \
+from       Tasks \
+where      RootTaskId = $1 \
+       and WorkspaceId = $2 \
+       and DeletedAt is null \
+union all \
+select     t.Id, \
+            t.RootTaskId \
+from       Tasks t \
+inner join cte \
+        on t.RootTaskId = cte.Id \
+where      t.DeletedAt is null \
+)";
+
+// Tasks come joined with their status and priority. `Status` and `Priority` are their positions, so sorting by them follows the workspace ordering.
+// Trashed tasks are filtered out before the join, so callers can keep appending their own joins and WHERE clauses
+const TASK_SELECT: &str = "SELECT t.*, \
+s.Name AS StatusName, s.Color AS StatusColor, s.Position AS Status, s.Category AS StatusCategory, \
+p.Name AS PriorityName, p.Color AS PriorityColor, p.Position AS Priority \
+FROM (SELECT * FROM Tasks WHERE DeletedAt IS NULL) t \
+INNER JOIN Statuses s ON s.Id = t.StatusId \
+INNER JOIN Priorities p ON p.Id = t.PriorityId";
+
+// The trashed counterpart of TASK_SELECT, `SubtaskCount` is everything trashed below the task
+const TRASH_SELECT: &str = "SELECT t.*, \
+s.Name AS StatusName, s.Color AS StatusColor, s.Position AS Status, s.Category AS StatusCategory, \
+p.Name AS PriorityName, p.Color AS PriorityColor, p.Position AS Priority, \
+(with recursive below (Id) as ( \
+select Id from Tasks where RootTaskId = t.Id and DeletedAt is not null \
+union all \
+select c.Id from Tasks c inner join below on c.RootTaskId = below.Id where c.DeletedAt is not null \
+) select COUNT(*) from below) AS SubtaskCount \
+FROM Tasks t \
+INNER JOIN Statuses s ON s.Id = t.StatusId \
+INNER JOIN Priorities p ON p.Id = t.PriorityId \
+WHERE t.DeletedAt IS NOT NULL";
+
+// Only the top of a trashed subtree shows up in the trash, its subtasks come back (or go away) together with it
+const TRASH_TOP_LEVEL: &str = "NOT EXISTS (SELECT 1 FROM Tasks r WHERE r.Id = t.RootTaskId AND r.DeletedAt IS NOT NULL)";
+
+// The trashed task $1 within $2 (workspace id) and everything trashed below it
+const TRASHED_CTE: &str = "with recursive trashed (Id) as ( \
+select     Id \
+from       Tasks \
+where      Id = $1 \
+       and WorkspaceId = $2 \
+       and DeletedAt is not null \
+union all \
+select     t.Id \
+from       Tasks t \
+inner join trashed \
+        on t.RootTaskId = trashed.Id \
+where      t.DeletedAt is not null \
+)";
+
+// The task $1 within $2 (workspace id) and everything below it down to $3 levels (no limit when null)
+const SUBTREE_CTE: &str = "with recursive tree (Id, Depth) as ( \
+select     Id, \
+            0 \
+from       Tasks \
+where      Id = $1 \
+       and WorkspaceId = $2 \
+       and DeletedAt is null \
+union all \
+select     t.Id, \
+            tree.Depth + 1 \
+from       Tasks t \
+inner join tree \
+        on t.RootTaskId = tree.Id \
+where      t.DeletedAt is null \
+       and ($3::int is null \
+        or tree.Depth < $3) \
+)";
+
+// The task $1 within $2 (workspace id) and its parents up to the top. A deleted parent sets RootTaskId to null, so a cut chain simply ends there
+const ANCESTORS_CTE: &str = "with recursive ancestors (Id, RootTaskId, Depth) as ( \
+select     Id, \
+            RootTaskId, \
+            0 \
+from       Tasks \
+where      Id = $1 \
+       and WorkspaceId = $2 \
+       and DeletedAt is null \
+union all \
+select     t.Id, \
+            t.RootTaskId, \
+            ancestors.Depth + 1 \
+from       Tasks t \
+inner join ancestors \
+        on t.Id = ancestors.RootTaskId \
+where      t.WorkspaceId = $2 \
+       and t.DeletedAt is null \
+)";
+
+const STATUS_INSERT: &str = "INSERT INTO Statuses (Id, WorkspaceId, Name, Color, Position, Category) VALUES ($1, $2, $3, $4, $5, $6)";
+
+const PRIORITY_INSERT: &str = "INSERT INTO Priorities (Id, WorkspaceId, Name, Color, Position) VALUES ($1, $2, $3, $4, $5)";
+
+const TASK_INSERT: &str = "INSERT INTO Tasks (Id, WorkspaceId, RootTaskId, Summary, Description, CreateDate, DueDate, Assignee, Resolution, AutoComplete, PriorityId, StatusId, Rank) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)";
+
+// Archived tasks only show up when asked for
+fn archived_clause(filter: &TaskFilter) -> &'static str {
+    if filter.include_archived { "" } else { " AND t.ArchivedAt IS NULL" }
+}
+
+// Keeps DoneDate in step with a change to the status bound as `param`: set when the task gets done, kept while it stays done, cleared on reopening
+fn done_date_assignment(param: usize) -> String {
+    format!(
+        "DoneDate = CASE WHEN (SELECT Category FROM Statuses WHERE Id = ${}) = {} THEN COALESCE(DoneDate, NOW()) ELSE NULL END",
+        param, convert::category_to_i16(domain::enums::StatusCategory::Done))
+}
+
+// A guarded write that touched nothing lost the race against another writer, an unguarded one didn't find the task
+fn missing_task(id: Uuid, expected_version: Option<i32>) -> Error {
+    match expected_version {
+        Some(_) => Error::precondition_failed(id),
+        None => Error::not_found(id)
+    }
+}
+
+// The first id is the task asked for and the only one checked against `expected_version`, the rest are its subtasks
+async fn trash_many_in(conn: &mut PgConnection, workspace_id: Uuid, ids: &[Uuid], deleted_at: DateTime<Utc>, deleted_by: Option<&str>, expected_version: Option<i32>) -> Result<(), Error> {
+    let affected = 
+        sqlx::query("UPDATE Tasks SET Version = Version + 1, DeletedAt = $1, DeletedBy = $2 WHERE Id = $3 AND WorkspaceId = $4 AND DeletedAt IS NULL AND ($5::int IS NULL OR Version = $5)")
+            .bind(deleted_at)
+            .bind(deleted_by)
+            .bind(ids[0])
+            .bind(workspace_id)
+            .bind(expected_version)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?
+            .rows_affected();
+
+    if affected == 0 {
+        return Err(missing_task(ids[0], expected_version));
+    }
+
+    sqlx::query("UPDATE Tasks SET Version = Version + 1, DeletedAt = $1, DeletedBy = $2 WHERE Id = ANY($3) AND WorkspaceId = $4 AND DeletedAt IS NULL")
+        .bind(deleted_at)
+        .bind(deleted_by)
+        .bind(&ids[1..])
+        .bind(workspace_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::DbError(e.to_string()))?;
+
+    Ok(())
+}
+
+// The task is trashed first so a stale version stops the subtasks from moving. They remember it, so a restore can take them back
+async fn trash_keeping_subtasks_in(conn: &mut PgConnection, workspace_id: Uuid, id: Uuid, new_root_id: Option<Uuid>, deleted_at: DateTime<Utc>, deleted_by: Option<&str>, expected_version: Option<i32>) -> Result<(), Error> {
+    trash_many_in(conn, workspace_id, &[id], deleted_at, deleted_by, expected_version).await?;
+
+    sqlx::query("UPDATE Tasks SET Version = Version + 1, TrashedParentId = RootTaskId, RootTaskId = $1 WHERE RootTaskId = $2 AND WorkspaceId = $3 AND DeletedAt IS NULL")
+        .bind(new_root_id)
+        .bind(id)
+        .bind(workspace_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::DbError(e.to_string()))?;
+
+    Ok(())
+}
+
+async fn update_task_in(conn: &mut PgConnection, workspace_id: Uuid, id: Uuid, changes: &TaskChanges) -> Result<(), Error> {
+    // $1 and $2 are the task, every given column takes the next parameter in the order they are bound below
+    let mut columns = vec![];
+    if changes.summary.is_some() { columns.push("Summary"); }
+    if changes.description.is_some() { columns.push("Description"); }
+    if changes.due_date.is_some() { columns.push("DueDate"); }
+    if changes.priority_id.is_some() { columns.push("PriorityId"); }
+    if changes.resolution.is_some() { columns.push("Resolution"); }
+    if changes.status_id.is_some() { columns.push("StatusId"); }
+
+    if columns.is_empty() {
+        return Ok(());
+    }
+
+    let mut assignments: Vec<String> = columns.iter().enumerate().map(|(i, c)| format!("{} = ${}", c, i + 3)).collect();
+    if changes.status_id.is_some() {
+        // StatusId always comes last
+        assignments.push(done_date_assignment(columns.len() + 2));
+    }
+
+    // The guard takes the parameter after the last column
+    let guard = match changes.expected_version {
+        Some(_) => format!(" AND Version = ${}", columns.len() + 3),
+        None => String::new()
+    };
+
+    let sql = format!("UPDATE Tasks SET Version = Version + 1, {} WHERE Id = $1 AND WorkspaceId = $2 AND DeletedAt IS NULL{}", assignments.join(", "), guard);
+    let mut query = sqlx::query(&sql).bind(id).bind(workspace_id);
+
+    if let Some(summary) = &changes.summary { query = query.bind(summary); }
+    if let Some(description) = &changes.description { query = query.bind(description); }
+    if let Some(due_date) = changes.due_date { query = query.bind(due_date); }
+    if let Some(priority_id) = changes.priority_id { query = query.bind(priority_id); }
+    if let Some(resolution) = &changes.resolution { query = query.bind(resolution); }
+    if let Some(status_id) = changes.status_id { query = query.bind(status_id); }
+    if let Some(expected_version) = changes.expected_version { query = query.bind(expected_version); }
+
+    let affected = 
+        query
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?
+            .rows_affected();
+
+    if affected > 0 { Ok(()) } else { Err(missing_task(id, changes.expected_version)) }
+}
+
+// Locks the task for the rest of the transaction, a trashed one counts as missing
+async fn lock_task_in(conn: &mut PgConnection, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+    let found = 
+        sqlx::query("SELECT Id FROM Tasks WHERE Id = $1 AND WorkspaceId = $2 AND DeletedAt IS NULL FOR UPDATE")
+            .bind(id)
+            .bind(workspace_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+    if found.is_some() { Ok(()) } else { Err(Error::not_found(id)) }
+}
+
+async fn add_tag_in(conn: &mut PgConnection, workspace_id: Uuid, id: Uuid, tag_id: Uuid) -> Result<(), Error> {
+    lock_task_in(conn, workspace_id, id).await?;
+
+    sqlx::query("INSERT INTO TaskTags (TaskId, TagId) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(id)
+        .bind(tag_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::DbError(e.to_string()))?;
+
+    Ok(())
+}
+
+async fn remove_tag_in(conn: &mut PgConnection, workspace_id: Uuid, id: Uuid, tag_id: Uuid) -> Result<(), Error> {
+    lock_task_in(conn, workspace_id, id).await?;
+
+    let affected = 
+        sqlx::query("DELETE FROM TaskTags WHERE TaskId = $1 AND TagId = $2")
+            .bind(id)
+            .bind(tag_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?
+            .rows_affected();
+
+    if affected > 0 { Ok(()) } else { Err(Error::not_found(tag_id)) }
+}
+
+// A task placed somewhere on purpose no longer goes back to a restored parent
+async fn update_task_root_in(conn: &mut PgConnection, workspace_id: Uuid, task_id: Uuid, new_root_id: Option<Uuid>, rank: &str, expected_version: Option<i32>) -> Result<(), Error> {
+    let affected = 
+        sqlx::query("UPDATE Tasks SET Version = Version + 1, RootTaskId = $1, Rank = $2, TrashedParentId = NULL WHERE Id = $3 AND WorkspaceId = $4 AND DeletedAt IS NULL AND ($5::int IS NULL OR Version = $5)")
+            .bind(new_root_id)
+            .bind(rank)
+            .bind(task_id)
+            .bind(workspace_id)
+            .bind(expected_version)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?
+            .rows_affected();
+
+    if affected > 0 { Ok(()) } else { Err(missing_task(task_id, expected_version)) }
+}
+
+// Tag ids are always bound as the parameter with index `param`
+fn tag_filter_clause(filter: &TaskFilter, param: usize) -> String {
+    if filter.tag_ids.is_empty() {
+        return String::new();
+    }
+
+    if filter.match_all_tags {
+        return format!(" AND t.Id IN (SELECT TaskId FROM TaskTags WHERE TagId = ANY(${}) GROUP BY TaskId HAVING COUNT(DISTINCT TagId) = CARDINALITY(${}))", param, param);
+    }
+
+    format!(" AND t.Id IN (SELECT TaskId FROM TaskTags WHERE TagId = ANY(${}))", param)
+}
+
+#[async_trait]
+impl WorkspaceRepository for WorkspaceStorage {
+    async fn get_by_id(&self, id: Uuid) -> Result<WorkspaceEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM Workspaces WHERE Id = $1")
+                .bind(id)
+                .map(|row: PgRow| {
+                    convert::row_to_workspace_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_all(&self) -> Vec<WorkspaceEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM Workspaces ORDER BY CreateDate")
+                .map(|row: PgRow| {
+                    convert::row_to_workspace_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn insert(&self, entity: WorkspaceEntity, statuses: Vec<StatusEntity>, priorities: Vec<PriorityEntity>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        sqlx::query("INSERT INTO Workspaces (Id, Name, CreateDate) VALUES ($1, $2, $3)")
+            .bind(entity.id)
+            .bind(entity.name)
+            .bind(entity.create_date)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        for status in statuses {
+            sqlx::query(STATUS_INSERT)
+                .bind(status.id)
+                .bind(status.workspace_id)
+                .bind(status.name)
+                .bind(status.color)
+                .bind(status.position)
+                .bind(convert::category_to_i16(status.category))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        for priority in priorities {
+            sqlx::query(PRIORITY_INSERT)
+                .bind(priority.id)
+                .bind(priority.workspace_id)
+                .bind(priority.name)
+                .bind(priority.color)
+                .bind(priority.position)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn update(&self, id: Uuid, name: &str) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Workspaces SET Name = $1 WHERE Id = $2")
+                .bind(name)
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Workspaces WHERE Id = $1")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+}
+
 #[async_trait]
 impl TaskRepository for TaskStorage {
-    async fn get_by_id(&self, id: Uuid) -> Result<TaskEntity, Error> {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TaskEntity, Error> {
         let result = 
-            sqlx::query("SELECT * FROM Tasks WHERE Id = $1")
+            sqlx::query(&format!("{} WHERE t.Id = $1 AND t.WorkspaceId = $2", TASK_SELECT))
                 .bind(id)
+                .bind(workspace_id)
                 .map(|row: PgRow| {
                     convert::row_to_task_entity(&row)
                 })
@@ -52,14 +586,20 @@ impl TaskRepository for TaskStorage {
 
     async fn insert(&self, entity: TaskEntity) -> Result<(), Error> {
         let result = 
-            sqlx::query("INSERT INTO Tasks (Id, Summary, Description, CreateDate, DueDate, Priority, Status) VALUES ($1, $2, $3, $4, $5, $6, $7)")
+            sqlx::query(TASK_INSERT)
                 .bind(entity.id)
+                .bind(entity.workspace_id)
+                .bind(entity.root_task_id)
                 .bind(entity.summary)
                 .bind(entity.description)
                 .bind(entity.create_date)
                 .bind(entity.due_date)
-                .bind(convert::priority_to_i16(entity.priority))
-                .bind(convert::status_to_i16(entity.status))
+                .bind(entity.assignee)
+                .bind(entity.resolution)
+                .bind(entity.auto_complete)
+                .bind(entity.priority.id)
+                .bind(entity.status.id)
+                .bind(entity.rank)
                 .execute(&self.pool)
                 .await;
 
@@ -70,10 +610,37 @@ impl TaskRepository for TaskStorage {
             Ok(())
         }
 
-    async fn delete(&self, id: Uuid) -> Result<(), Error> {
+    async fn insert_many(&self, entities: Vec<TaskEntity>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        for entity in entities {
+            sqlx::query(TASK_INSERT)
+                .bind(entity.id)
+                .bind(entity.workspace_id)
+                .bind(entity.root_task_id)
+                .bind(entity.summary)
+                .bind(entity.description)
+                .bind(entity.create_date)
+                .bind(entity.due_date)
+                .bind(entity.assignee)
+                .bind(entity.resolution)
+                .bind(entity.auto_complete)
+                .bind(entity.priority.id)
+                .bind(entity.status.id)
+                .bind(entity.rank)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
         let affected = 
-            sqlx::query("DELETE FROM Tasks WHERE Id = $1")
+            sqlx::query("DELETE FROM Tasks WHERE Id = $1 AND WorkspaceId = $2")
                 .bind(id)
+                .bind(workspace_id)
                 .execute(&self.pool)
                 .await
                 .unwrap()
@@ -82,10 +649,27 @@ impl TaskRepository for TaskStorage {
         if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
     }
 
-    async fn get_subtasks(&self, task_id: Uuid) -> Vec<TaskEntity> {
+    async fn trash_many(&self, workspace_id: Uuid, ids: &[Uuid], deleted_at: DateTime<Utc>, deleted_by: Option<&str>, expected_version: Option<i32>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        trash_many_in(&mut tx, workspace_id, ids, deleted_at, deleted_by, expected_version).await?;
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn trash_keeping_subtasks(&self, workspace_id: Uuid, id: Uuid, new_root_id: Option<Uuid>, deleted_at: DateTime<Utc>, deleted_by: Option<&str>, expected_version: Option<i32>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        trash_keeping_subtasks_in(&mut tx, workspace_id, id, new_root_id, deleted_at, deleted_by, expected_version).await?;
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn get_subtasks(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<TaskEntity> {
         let result = 
-            sqlx::query("SELECT * FROM Tasks WHERE RootTaskId = $1")
+            sqlx::query(&format!("{} WHERE t.RootTaskId = $1 AND t.WorkspaceId = $2 ORDER BY t.Rank, t.CreateDate, t.Id", TASK_SELECT))
                 .bind(task_id)
+                .bind(workspace_id)
                 .map(|row: PgRow| {
                     convert::row_to_task_entity(&row)
                 })
@@ -95,13 +679,15 @@ impl TaskRepository for TaskStorage {
         result.unwrap_or(vec![])
     }
 
-    async fn get_root_task_batch(&self, take: i32, continuation_token: &str, sort_by: &str, descending: bool) -> (Vec<domain::models::TaskEntity>, String) {
+    async fn get_root_task_batch(&self, workspace_id: Uuid, filter: &TaskFilter, take: i32, continuation_token: &str, sort_by: &str, descending: bool) -> (Vec<domain::models::TaskEntity>, String) {
         let sort = if descending { "DESC" } else { "ASC" };
         let skip = continuation_token.parse::<i32>().unwrap();
         let entities = 
-            sqlx::query(format!("SELECT * FROM Tasks WHERE RootTaskId IS NULL ORDER BY {} {} LIMIT $1 OFFSET $2", sort_by, sort).as_str())
+            sqlx::query(format!("{} WHERE t.RootTaskId IS NULL AND t.WorkspaceId = $1{}{} ORDER BY {} {} LIMIT $2 OFFSET $3", TASK_SELECT, archived_clause(filter), tag_filter_clause(filter, 4), sort_by, sort).as_str())
+                .bind(workspace_id)
                 .bind(take)
                 .bind(skip)
+                .bind(&filter.tag_ids)
                 .map(|row: PgRow| {
                     convert::row_to_task_entity(&row)
                 })
@@ -114,14 +700,16 @@ impl TaskRepository for TaskStorage {
         (entities, skip.to_string())
     }
 
-    async fn search_tasks(&self, phrase: &str, take: i32, continuation_token: &str) -> (Vec<domain::models::TaskSearchEntity>, String) {
+    async fn search_tasks(&self, workspace_id: Uuid, filter: &TaskFilter, phrase: &str, take: i32, continuation_token: &str) -> (Vec<domain::models::TaskSearchEntity>, String) {
         let skip = continuation_token.parse::<i32>().unwrap();
         let entities = 
-            sqlx::query("SELECT Id, Summary, Description FROM Tasks WHERE RootTaskId IS NULL AND (Summary ILIKE $1 OR Description ILIKE $2) ORDER BY CreateDate LIMIT $3 OFFSET $4")
+            sqlx::query(format!("SELECT t.Id, t.Summary, t.Description FROM Tasks t WHERE t.RootTaskId IS NULL AND t.DeletedAt IS NULL AND t.WorkspaceId = $1 AND (t.Summary ILIKE $2 OR t.Description ILIKE $3){}{} ORDER BY t.CreateDate LIMIT $4 OFFSET $5", archived_clause(filter), tag_filter_clause(filter, 6)).as_str())
+                .bind(workspace_id)
                 .bind(format!("%{}%", phrase))
                 .bind(format!("%{}%", phrase))
                 .bind(take)
                 .bind(skip)
+                .bind(&filter.tag_ids)
                 .map(|row: PgRow| {
                     convert::row_to_task_search_entity(&row)
                 })
@@ -134,23 +722,12 @@ impl TaskRepository for TaskStorage {
         (entities, skip.to_string())
     }
 
-    async fn get_all_subtasks_recursive(&self, task_id: Uuid) -> Vec<Uuid> {
-        let result = sqlx::query(format!("with recursive cte (Id, RootTaskId) as ( \
-select     Id, \
-            RootTaskId \
-from       todolist.Tasks \
-where      RootTaskId = '{}' \
-union all \
-select     t.Id, \
-            t.RootTaskId \
-from       todolist.Tasks t \
-inner join cte \
-        on t.RootTaskId = cte.Id \
-) \
-select cte.Id as val from cte;", task_id.to_string()).as_str())
-            .bind(task_id.to_string())
+    async fn get_all_subtasks_recursive(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<Uuid> {
+        let result = sqlx::query(&format!("{} select cte.Id as val from cte;", SUBTASKS_CTE))
+            .bind(task_id)
+            .bind(workspace_id)
             .map(|row: PgRow| {
-                Uuid::parse_str(row.get("val")).unwrap()
+                row.get::<Uuid, _>("val")
             })
             .fetch_all(&self.pool)
             .await;
@@ -158,93 +735,1625 @@ select cte.Id as val from cte;", task_id.to_string()).as_str())
         result.unwrap_or(vec![])
     }
 
-    async fn update_task_root(&self, task_id: Uuid, new_root_id: Option<Uuid>) -> Result<(), app::errors::Error> {
-        let affected = 
-            sqlx::query("UPDATE Tasks SET RootTaskId = $1 WHERE Id = $2")
-                .bind(new_root_id)
-                .bind(task_id)
-                .execute(&self.pool)
-                .await
-                .unwrap()
-                .rows_affected();
-
-        if affected > 0 { Ok(()) } else { Err(Error::EntityNotFound(task_id.to_string())) }
-    }
-
-    async fn update_task(&self, id: Uuid, summary: &str, description: Option<&str>, due_date: DateTime<Utc>, priority: domain::enums::TaskPriority, status: domain::enums::TaskStatus) -> Result<(), app::errors::Error> {
+    async fn get_subtasks_recursive(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<TaskEntity> {
+        let result = sqlx::query(&format!("{} {} inner join cte on t.Id = cte.Id;", SUBTASKS_CTE, TASK_SELECT))
+            .bind(task_id)
+            .bind(workspace_id)
+            .map(|row: PgRow| {
+                convert::row_to_task_entity(&row)
+            })
+            .fetch_all(&self.pool)
+            .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn get_ancestors(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<TaskEntity> {
+        let result = sqlx::query(&format!("{} {} inner join ancestors on t.Id = ancestors.Id where ancestors.Depth > 0 order by ancestors.Depth desc;", ANCESTORS_CTE, TASK_SELECT))
+            .bind(task_id)
+            .bind(workspace_id)
+            .map(|row: PgRow| {
+                convert::row_to_task_entity(&row)
+            })
+            .fetch_all(&self.pool)
+            .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn get_subtree(&self, workspace_id: Uuid, task_id: Uuid, max_depth: Option<i32>) -> Vec<TaskTreeNodeEntity> {
+        let sql = format!(
+            "{} SELECT n.*, tree.Depth, (SELECT COUNT(*) FROM Tasks c WHERE c.RootTaskId = n.Id AND c.DeletedAt IS NULL) AS ChildCount FROM ({}) n INNER JOIN tree ON tree.Id = n.Id ORDER BY tree.Depth, n.Rank, n.CreateDate, n.Id",
+            SUBTREE_CTE, TASK_SELECT);
+
+        let result = sqlx::query(&sql)
+            .bind(task_id)
+            .bind(workspace_id)
+            .bind(max_depth)
+            .map(|row: PgRow| {
+                convert::row_to_task_tree_node_entity(&row)
+            })
+            .fetch_all(&self.pool)
+            .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn count_subtasks_by_status(&self, workspace_id: Uuid, task_id: Uuid) -> Vec<(StatusEntity, i64)> {
+        let result = sqlx::query(&format!("{} select s.*, count(*) as val from cte inner join Tasks t on t.Id = cte.Id inner join Statuses s on s.Id = t.StatusId group by s.Id order by s.Position, s.Name;", SUBTASKS_CTE))
+            .bind(task_id)
+            .bind(workspace_id)
+            .map(|row: PgRow| {
+                (convert::row_to_status_entity(&row), row.get::<i64, _>("val"))
+            })
+            .fetch_all(&self.pool)
+            .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn update_due_dates(&self, workspace_id: Uuid, due_dates: &[(Uuid, DateTime<Utc>)]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        for (id, due_date) in due_dates {
+            let affected = 
+                sqlx::query("UPDATE Tasks SET Version = Version + 1, DueDate = $1 WHERE Id = $2 AND WorkspaceId = $3 AND DeletedAt IS NULL")
+                    .bind(due_date)
+                    .bind(id)
+                    .bind(workspace_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| Error::DbError(e.to_string()))?
+                    .rows_affected();
+
+            if affected == 0 {
+                return Err(Error::not_found(*id));
+            }
+        }
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn update_assignee(&self, workspace_id: Uuid, id: Uuid, assignee: Option<&str>) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Tasks SET Version = Version + 1, Assignee = $1 WHERE Id = $2 AND WorkspaceId = $3 AND DeletedAt IS NULL")
+                .bind(assignee)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn update_resolution(&self, workspace_id: Uuid, id: Uuid, resolution: Option<&str>) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Tasks SET Version = Version + 1, Resolution = $1 WHERE Id = $2 AND WorkspaceId = $3 AND DeletedAt IS NULL")
+                .bind(resolution)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn update_status(&self, workspace_id: Uuid, id: Uuid, status_id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query(&format!("UPDATE Tasks SET Version = Version + 1, {}, StatusId = $1 WHERE Id = $2 AND WorkspaceId = $3 AND DeletedAt IS NULL", done_date_assignment(1)))
+                .bind(status_id)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn update_auto_complete(&self, workspace_id: Uuid, id: Uuid, auto_complete: bool) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Tasks SET Version = Version + 1, AutoComplete = $1 WHERE Id = $2 AND WorkspaceId = $3 AND DeletedAt IS NULL")
+                .bind(auto_complete)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    // Trashed siblings count as well, so a restored task doesn't end up tied with another one
+    async fn get_last_rank(&self, workspace_id: Uuid, root_task_id: Option<Uuid>) -> Option<String> {
+        sqlx::query("SELECT Rank AS val FROM Tasks WHERE WorkspaceId = $1 AND RootTaskId IS NOT DISTINCT FROM $2 ORDER BY Rank DESC LIMIT 1")
+            .bind(workspace_id)
+            .bind(root_task_id)
+            .map(|row: PgRow| {
+                row.get::<String, _>("val")
+            })
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap_or(None)
+    }
+
+    async fn get_adjacent_rank(&self, workspace_id: Uuid, root_task_id: Option<Uuid>, rank: &str, after: bool, except_id: Uuid) -> Option<String> {
+        let sql = if after {
+            "SELECT Rank AS val FROM Tasks WHERE WorkspaceId = $1 AND RootTaskId IS NOT DISTINCT FROM $2 AND Rank > $3 AND Id <> $4 ORDER BY Rank LIMIT 1"
+        } else {
+            "SELECT Rank AS val FROM Tasks WHERE WorkspaceId = $1 AND RootTaskId IS NOT DISTINCT FROM $2 AND Rank < $3 AND Id <> $4 ORDER BY Rank DESC LIMIT 1"
+        };
+
+        sqlx::query(sql)
+            .bind(workspace_id)
+            .bind(root_task_id)
+            .bind(rank)
+            .bind(except_id)
+            .map(|row: PgRow| {
+                row.get::<String, _>("val")
+            })
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap_or(None)
+    }
+
+    async fn update_archived(&self, workspace_id: Uuid, ids: &[Uuid], archived_at: Option<DateTime<Utc>>) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Tasks SET Version = Version + 1, ArchivedAt = $1 WHERE Id = ANY($2) AND WorkspaceId = $3 AND DeletedAt IS NULL")
+                .bind(archived_at)
+                .bind(ids)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(ids[0])) }
+    }
+
+    // A tree counts as done when none of its tasks is unfinished or got done after the date. Tasks created as done have no DoneDate, their create date stands in
+    async fn get_done_trees_before(&self, done_before: DateTime<Utc>) -> Vec<TaskEntity> {
+        let sql = format!(
+            "{} WHERE t.RootTaskId IS NULL AND t.ArchivedAt IS NULL AND NOT EXISTS ( \
+with recursive tree (Id) as ( \
+select t.Id \
+union all \
+select c.Id from Tasks c inner join tree on c.RootTaskId = tree.Id where c.DeletedAt is null \
+) \
+select 1 from tree inner join Tasks x on x.Id = tree.Id inner join Statuses xs on xs.Id = x.StatusId \
+where xs.Category <> $1 or COALESCE(x.DoneDate, x.CreateDate) > $2)",
+            TASK_SELECT);
+
+        let result = sqlx::query(&sql)
+            .bind(convert::category_to_i16(domain::enums::StatusCategory::Done))
+            .bind(done_before)
+            .map(|row: PgRow| {
+                convert::row_to_task_entity(&row)
+            })
+            .fetch_all(&self.pool)
+            .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn update_task_root(&self, workspace_id: Uuid, task_id: Uuid, new_root_id: Option<Uuid>, rank: &str, expected_version: Option<i32>) -> Result<(), app::errors::Error> {
+        let mut conn = self.pool.acquire().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        update_task_root_in(&mut conn, workspace_id, task_id, new_root_id, rank, expected_version).await
+    }
+
+    async fn update_task(&self, workspace_id: Uuid, id: Uuid, changes: &TaskChanges) -> Result<(), app::errors::Error> {
+        let mut conn = self.pool.acquire().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        update_task_in(&mut conn, workspace_id, id, changes).await
+    }
+
+    async fn write_all(&self, workspace_id: Uuid, writes: &[TaskWrite], deleted_at: DateTime<Utc>, deleted_by: Option<&str>) -> Result<(), (usize, Error)> {
+        let mut tx = self.pool.begin().await.map_err(|e| (0, Error::DbError(e.to_string())))?;
+
+        for (index, write) in writes.iter().enumerate() {
+            let result = match write {
+                TaskWrite::Update { id, changes } => update_task_in(&mut tx, workspace_id, *id, changes).await,
+                TaskWrite::Root { id, root_task_id, rank, expected_version } => update_task_root_in(&mut tx, workspace_id, *id, *root_task_id, rank, *expected_version).await,
+                TaskWrite::Trash { ids, expected_version } => trash_many_in(&mut tx, workspace_id, ids, deleted_at, deleted_by, *expected_version).await,
+                TaskWrite::TrashKeepingSubtasks { id, new_root_id, expected_version } => trash_keeping_subtasks_in(&mut tx, workspace_id, *id, *new_root_id, deleted_at, deleted_by, *expected_version).await,
+                TaskWrite::AddTag { id, tag_id } => add_tag_in(&mut tx, workspace_id, *id, *tag_id).await,
+                TaskWrite::RemoveTag { id, tag_id } => remove_tag_in(&mut tx, workspace_id, *id, *tag_id).await
+            };
+
+            // Dropping the transaction rolls back whatever came before
+            result.map_err(|e| (index, e))?;
+        }
+
+        tx.commit().await.map_err(|e| (writes.len(), Error::DbError(e.to_string())))
+    }
+}
+
+#[async_trait]
+impl StatusRepository for StatusStorage {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<StatusEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM Statuses WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_status_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<StatusEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM Statuses WHERE WorkspaceId = $1 ORDER BY Position, Name")
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_status_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn insert(&self, entity: StatusEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query(STATUS_INSERT)
+                .bind(entity.id)
+                .bind(entity.workspace_id)
+                .bind(entity.name)
+                .bind(entity.color)
+                .bind(entity.position)
+                .bind(convert::category_to_i16(entity.category))
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, color: &str, position: i32, category: domain::enums::StatusCategory) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Statuses SET Name = $1, Color = $2, Position = $3, Category = $4 WHERE Id = $5 AND WorkspaceId = $6")
+                .bind(name)
+                .bind(color)
+                .bind(position)
+                .bind(convert::category_to_i16(category))
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Statuses WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn count_tasks(&self, id: Uuid) -> i64 {
+        sqlx::query("SELECT COUNT(*) AS val FROM Tasks WHERE StatusId = $1")
+            .bind(id)
+            .map(|row: PgRow| {
+                row.get::<i64, _>("val")
+            })
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl PriorityRepository for PriorityStorage {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<PriorityEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM Priorities WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_priority_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<PriorityEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM Priorities WHERE WorkspaceId = $1 ORDER BY Position, Name")
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_priority_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn insert(&self, entity: PriorityEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query(PRIORITY_INSERT)
+                .bind(entity.id)
+                .bind(entity.workspace_id)
+                .bind(entity.name)
+                .bind(entity.color)
+                .bind(entity.position)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, color: &str, position: i32) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE Priorities SET Name = $1, Color = $2, Position = $3 WHERE Id = $4 AND WorkspaceId = $5")
+                .bind(name)
+                .bind(color)
+                .bind(position)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Priorities WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn count_tasks(&self, id: Uuid) -> i64 {
+        sqlx::query("SELECT COUNT(*) AS val FROM Tasks WHERE PriorityId = $1")
+            .bind(id)
+            .map(|row: PgRow| {
+                row.get::<i64, _>("val")
+            })
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl TagRepository for TagStorage {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TagEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM Tags WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_tag_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<TagEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM Tags WHERE WorkspaceId = $1 ORDER BY Name")
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_tag_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn insert(&self, entity: TagEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO Tags (Id, WorkspaceId, Name, Color) VALUES ($1, $2, $3, $4)")
+                .bind(entity.id)
+                .bind(entity.workspace_id)
+                .bind(entity.name)
+                .bind(entity.color)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, color: &str) -> Result<(), Error> {
+        let result = 
+            sqlx::query("UPDATE Tags SET Name = $1, Color = $2 WHERE Id = $3 AND WorkspaceId = $4")
+                .bind(name)
+                .bind(color)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await;
+
+        match result {
+            Ok(r) if r.rows_affected() > 0 => Ok(()),
+            Ok(_) => Err(Error::not_found(id)),
+            Err(err) => Err(Error::DbError(err.to_string()))
+        }
+    }
+
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Tags WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn add_to_task(&self, task_id: Uuid, tag_id: Uuid) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO TaskTags (TaskId, TagId) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+                .bind(task_id)
+                .bind(tag_id)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn remove_from_task(&self, task_id: Uuid, tag_id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM TaskTags WHERE TaskId = $1 AND TagId = $2")
+                .bind(task_id)
+                .bind(tag_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(tag_id)) }
+    }
+
+    async fn get_by_tasks(&self, task_ids: &[Uuid]) -> Vec<(Uuid, TagEntity)> {
+        let result = 
+            sqlx::query("SELECT tt.TaskId, t.* FROM TaskTags tt INNER JOIN Tags t ON t.Id = tt.TagId WHERE tt.TaskId = ANY($1) ORDER BY t.Name")
+                .bind(task_ids)
+                .map(|row: PgRow| {
+                    (row.get("taskid"), convert::row_to_tag_entity(&row))
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+}
+
+#[async_trait]
+impl CommentRepository for CommentStorage {
+    async fn get_by_id(&self, task_id: Uuid, id: Uuid) -> Result<CommentEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM Comments WHERE Id = $1 AND TaskId = $2")
+                .bind(id)
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_comment_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_batch_by_task(&self, task_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> (Vec<CommentEntity>, String) {
+        let skip = continuation_token.parse::<i32>().unwrap();
+        let sort = if descending { "DESC" } else { "ASC" };
+
+        let entities = 
+            sqlx::query(format!("SELECT * FROM Comments WHERE TaskId = $1 ORDER BY CreateDate {} LIMIT $2 OFFSET $3", sort).as_str())
+                .bind(task_id)
+                .bind(take)
+                .bind(skip)
+                .map(|row: PgRow| {
+                    convert::row_to_comment_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await
+                .unwrap_or(vec![]);
+
+        let skip = if {entities.len() as i32} < take { skip + entities.len() as i32 } else { skip + take };
+
+        (entities, skip.to_string())
+    }
+
+    async fn insert(&self, entity: CommentEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO Comments (Id, TaskId, Author, Body, CreateDate) VALUES ($1, $2, $3, $4, $5)")
+                .bind(entity.id)
+                .bind(entity.task_id)
+                .bind(entity.author)
+                .bind(entity.body)
+                .bind(entity.create_date)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn update(&self, task_id: Uuid, id: Uuid, body: &str, update_date: DateTime<Utc>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        // The revision keeps the body being replaced, dated by its own last change
+        let archived = 
+            sqlx::query("INSERT INTO CommentRevisions (Id, CommentId, Body, CreateDate) SELECT $1, Id, Body, COALESCE(UpdateDate, CreateDate) FROM Comments WHERE Id = $2 AND TaskId = $3")
+                .bind(Uuid::new_v4())
+                .bind(id)
+                .bind(task_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?
+                .rows_affected();
+
+        if archived == 0 {
+            return Err(Error::not_found(id));
+        }
+
+        sqlx::query("UPDATE Comments SET Body = $1, UpdateDate = $2 WHERE Id = $3")
+            .bind(body)
+            .bind(update_date)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn delete(&self, task_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Comments WHERE Id = $1 AND TaskId = $2")
+                .bind(id)
+                .bind(task_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn get_revisions(&self, comment_id: Uuid) -> Vec<CommentRevisionEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM CommentRevisions WHERE CommentId = $1 ORDER BY CreateDate")
+                .bind(comment_id)
+                .map(|row: PgRow| {
+                    convert::row_to_comment_revision_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for AttachmentStorage {
+    async fn get_by_id(&self, task_id: Uuid, id: Uuid) -> Result<AttachmentEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM Attachments WHERE Id = $1 AND TaskId = $2")
+                .bind(id)
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_attachment_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_by_task(&self, task_id: Uuid) -> Vec<AttachmentEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM Attachments WHERE TaskId = $1 ORDER BY CreateDate")
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_attachment_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn insert(&self, entity: AttachmentEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO Attachments (Id, TaskId, FileName, ContentType, Size, Hash, CreateDate) VALUES ($1, $2, $3, $4, $5, $6, $7)")
+                .bind(entity.id)
+                .bind(entity.task_id)
+                .bind(entity.file_name)
+                .bind(entity.content_type)
+                .bind(entity.size)
+                .bind(entity.hash)
+                .bind(entity.create_date)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, task_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Attachments WHERE Id = $1 AND TaskId = $2")
+                .bind(id)
+                .bind(task_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn count_by_hash(&self, hash: &str) -> i64 {
+        sqlx::query("SELECT COUNT(*) AS val FROM Attachments WHERE Hash = $1")
+            .bind(hash)
+            .map(|row: PgRow| {
+                row.get::<i64, _>("val")
+            })
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or(0)
+    }
+
+    async fn get_hashes_by_workspace(&self, workspace_id: Uuid) -> Vec<String> {
+        let result = 
+            sqlx::query("SELECT DISTINCT a.Hash FROM Attachments a INNER JOIN Tasks t ON t.Id = a.TaskId WHERE t.WorkspaceId = $1")
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    row.get::<String, _>("hash")
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn lock_hash(&self, hash: &str) -> Result<Box<dyn HashLock>, Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1, hashtext($2))")
+            .bind(ATTACHMENT_LOCK_NAMESPACE)
+            .bind(hash)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        Ok(Box::new(TransactionLock(tx)))
+    }
+}
+
+// Advisory locks share one key space per database: attachment locks take this ("ATCH" in ASCII) as the first key
+// and hashtext(hash) as the second, so each hash gets its own lock that can't collide with locks taken for other things
+const ATTACHMENT_LOCK_NAMESPACE: i32 = 0x4154_4348;
+
+// A transaction level advisory lock, gone with the transaction whether it is committed or dropped
+struct TransactionLock(Transaction<'static, Postgres>);
+
+#[async_trait]
+impl HashLock for TransactionLock {
+    async fn release(self: Box<Self>) {
+        let _ = self.0.commit().await;
+    }
+}
+
+#[async_trait]
+impl ChecklistRepository for ChecklistStorage {
+    async fn get_by_id(&self, task_id: Uuid, id: Uuid) -> Result<ChecklistItemEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM ChecklistItems WHERE Id = $1 AND TaskId = $2")
+                .bind(id)
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_checklist_item_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_by_task(&self, task_id: Uuid) -> Vec<ChecklistItemEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM ChecklistItems WHERE TaskId = $1 ORDER BY Position")
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_checklist_item_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn insert(&self, entity: ChecklistItemEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO ChecklistItems (Id, TaskId, Text, Done, Position) VALUES ($1, $2, $3, $4, $5)")
+                .bind(entity.id)
+                .bind(entity.task_id)
+                .bind(entity.text)
+                .bind(entity.done)
+                .bind(entity.position)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn set_done(&self, task_id: Uuid, id: Uuid, done: bool) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE ChecklistItems SET Done = $1 WHERE Id = $2 AND TaskId = $3")
+                .bind(done)
+                .bind(id)
+                .bind(task_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn set_positions(&self, task_id: Uuid, ordered_ids: &[Uuid]) -> Result<(), Error> {
+        // Positions are assigned from the array index in a single statement
+        let result = 
+            sqlx::query("UPDATE ChecklistItems c SET Position = o.Position - 1 FROM UNNEST($1::uuid[]) WITH ORDINALITY AS o(Id, Position) WHERE c.Id = o.Id AND c.TaskId = $2")
+                .bind(ordered_ids)
+                .bind(task_id)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, task_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM ChecklistItems WHERE Id = $1 AND TaskId = $2")
+                .bind(id)
+                .bind(task_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+}
+
+#[async_trait]
+impl LogRepository for LogStorage {
+    async fn insert(&self, entity: LogEntity) {
+        let _ = 
+            sqlx::query("INSERT INTO Logs (Id, Action, TimestampMsec, EntityId, EntityType, Payload) VALUES ($1, $2, $3, $4, $5, $6)")
+                .bind(entity.id)
+                .bind(convert::action_to_i16(entity.action))
+                .bind(entity.timestamp)
+                .bind(entity.entity_id)
+                .bind(entity.entity_type)
+                .bind(entity.payload)
+                .execute(&self.pool)
+                .await;
+    }
+
+    async fn get_batch_by_entity_type(&self, entity_type: &str, continuation_token: &str, take: i32, descending: bool) -> (Vec<domain::models::LogEntity>, String) {
+        let skip = continuation_token.parse::<i32>().unwrap();
+        let sort = if descending { "DESC" } else { "ASC" };
+
+        let entities = 
+            sqlx::query(format!("SELECT * FROM Logs WHERE EntityType = $1 ORDER BY TimestampMsec {} LIMIT $2 OFFSET $3", sort).as_str())
+                .bind(entity_type)
+                .bind(take)
+                .bind(skip)
+                .map(|row: PgRow| {
+                    convert::row_to_log_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await
+                .unwrap();
+
+        let skip = if {entities.len() as i32} < take { skip + entities.len() as i32 } else { skip + take };
+
+        (entities, skip.to_string())
+    }
+
+    async fn get_batch_by_entity(&self, entity_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> (Vec<domain::models::LogEntity>, String) {
+        let skip = continuation_token.parse::<i32>().unwrap();
+        let sort = if descending { "DESC" } else { "ASC" };
+
+        let entities = 
+            sqlx::query(format!("SELECT * FROM Logs WHERE EntityId = $1 ORDER BY TimestampMsec {} LIMIT $2 OFFSET $3", sort).as_str())
+                .bind(entity_id)
+                .bind(take)
+                .bind(skip)
+                .map(|row: PgRow| {
+                    convert::row_to_log_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await
+                .unwrap();
+
+        let skip = if {entities.len() as i32} < take { skip + entities.len() as i32 } else { skip + take };
+
+        (entities, skip.to_string())
+    }
+}
+#[async_trait]
+impl DependencyRepository for DependencyStorage {
+    async fn add(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO TaskDependencies (BlockerId, BlockedId) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+                .bind(blocker_id)
+                .bind(blocked_id)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, blocker_id: Uuid, blocked_id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM TaskDependencies WHERE BlockerId = $1 AND BlockedId = $2")
+                .bind(blocker_id)
+                .bind(blocked_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(blocker_id)) }
+    }
+
+    async fn get_blockers(&self, task_id: Uuid) -> Vec<TaskEntity> {
+        let result = 
+            sqlx::query(&format!("{} INNER JOIN TaskDependencies d ON t.Id = d.BlockerId WHERE d.BlockedId = $1 ORDER BY t.DueDate", TASK_SELECT))
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_task_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn get_dependents(&self, task_id: Uuid) -> Vec<TaskEntity> {
+        let result = 
+            sqlx::query(&format!("{} INNER JOIN TaskDependencies d ON t.Id = d.BlockedId WHERE d.BlockerId = $1 ORDER BY t.DueDate", TASK_SELECT))
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_task_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    // UNION (not UNION ALL) keeps the walk finite even if the graph somehow got a cycle
+    async fn get_all_dependents_recursive(&self, task_id: Uuid) -> Vec<Uuid> {
+        let result = sqlx::query("with recursive cte (Id) as ( \
+select     BlockedId \
+from       TaskDependencies \
+where      BlockerId = $1 \
+union \
+select     d.BlockedId \
+from       TaskDependencies d \
+inner join cte \
+        on d.BlockerId = cte.Id \
+) \
+select cte.Id as val from cte;")
+            .bind(task_id)
+            .map(|row: PgRow| {
+                row.get::<Uuid, _>("val")
+            })
+            .fetch_all(&self.pool)
+            .await;
+
+        result.unwrap_or(vec![])
+    }
+}
+
+#[async_trait]
+impl RecurrenceRepository for RecurrenceStorage {
+    async fn get_by_task(&self, task_id: Uuid) -> Option<RecurrenceEntity> {
+        sqlx::query("SELECT * FROM TaskRecurrences WHERE TaskId = $1")
+            .bind(task_id)
+            .map(|row: PgRow| {
+                convert::row_to_recurrence_entity(&row)
+            })
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap()
+    }
+
+    async fn upsert(&self, entity: RecurrenceEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO TaskRecurrences (TaskId, Rule, CloneSubtasks, Occurrence, PreviousTaskId, NextTaskId) VALUES ($1, $2, $3, $4, $5, $6) \
+                         ON CONFLICT (TaskId) DO UPDATE SET Rule = EXCLUDED.Rule, CloneSubtasks = EXCLUDED.CloneSubtasks")
+                .bind(entity.task_id)
+                .bind(entity.rule)
+                .bind(entity.clone_subtasks)
+                .bind(entity.occurrence)
+                .bind(entity.previous_task_id)
+                .bind(entity.next_task_id)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn set_next(&self, task_id: Uuid, next_task_id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE TaskRecurrences SET NextTaskId = $1 WHERE TaskId = $2")
+                .bind(next_task_id)
+                .bind(task_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(task_id)) }
+    }
+
+    async fn delete(&self, task_id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM TaskRecurrences WHERE TaskId = $1")
+                .bind(task_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(task_id)) }
+    }
+}
+
+#[async_trait]
+impl ReminderRepository for ReminderStorage {
+    async fn get_unfinished_due_before(&self, until: DateTime<Utc>) -> Vec<TaskEntity> {
+        let result = 
+            sqlx::query(&format!("{} WHERE t.DueDate <= $1 AND s.Category <> $2 ORDER BY t.DueDate", TASK_SELECT))
+                .bind(until)
+                .bind(convert::category_to_i16(domain::enums::StatusCategory::Done))
+                .map(|row: PgRow| {
+                    convert::row_to_task_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn try_insert(&self, entity: ReminderEntity) -> Result<bool, Error> {
+        let result = 
+            sqlx::query("INSERT INTO FiredReminders (Id, TaskId, Kind, DueDate, FireDate) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (TaskId, Kind, DueDate) DO NOTHING")
+                .bind(entity.id)
+                .bind(entity.task_id)
+                .bind(entity.kind)
+                .bind(entity.due_date)
+                .bind(entity.fire_date)
+                .execute(&self.pool)
+                .await;
+
+        match result {
+            Ok(r) => Ok(r.rows_affected() > 0),
+            Err(err) => Err(Error::DbError(err.to_string()))
+        }
+    }
+
+    async fn get_by_task(&self, task_id: Uuid) -> Vec<ReminderEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM FiredReminders WHERE TaskId = $1 ORDER BY FireDate")
+                .bind(task_id)
+                .map(|row: PgRow| {
+                    convert::row_to_reminder_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationStorage {
+    async fn get_preferences(&self, email: &str) -> Option<NotificationPreferenceEntity> {
+        sqlx::query("SELECT * FROM NotificationPreferences WHERE Email = $1")
+            .bind(email)
+            .map(|row: PgRow| {
+                convert::row_to_notification_preference_entity(&row)
+            })
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap()
+    }
+
+    async fn upsert_preferences(&self, entity: NotificationPreferenceEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO NotificationPreferences (Email, Assignment, StatusChange, Comments, DueSoon, Digest) VALUES ($1, $2, $3, $4, $5, $6) \
+                         ON CONFLICT (Email) DO UPDATE SET Assignment = EXCLUDED.Assignment, StatusChange = EXCLUDED.StatusChange, Comments = EXCLUDED.Comments, DueSoon = EXCLUDED.DueSoon, Digest = EXCLUDED.Digest")
+                .bind(entity.email)
+                .bind(entity.assignment)
+                .bind(entity.status_change)
+                .bind(entity.comments)
+                .bind(entity.due_soon)
+                .bind(entity.digest)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn enqueue(&self, entity: OutboxMessageEntity) -> Result<(), Error> {
+        let result = 
+            sqlx::query("INSERT INTO EmailOutbox (Id, Recipient, Subject, TextBody, HtmlBody, Digest, CreateDate, Attempts, NextAttemptDate) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)")
+                .bind(entity.id)
+                .bind(entity.recipient)
+                .bind(entity.subject)
+                .bind(entity.text_body)
+                .bind(entity.html_body)
+                .bind(entity.digest)
+                .bind(entity.create_date)
+                .bind(entity.attempts)
+                .bind(entity.next_attempt_date)
+                .execute(&self.pool)
+                .await;
+
+        if let Err(err) = result {
+            return Err(Error::DbError(err.to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn get_pending(&self, now: DateTime<Utc>, max_attempts: i32, take: i32) -> Vec<OutboxMessageEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM EmailOutbox WHERE SentDate IS NULL AND NOT Digest AND Attempts < $1 AND NextAttemptDate <= $2 ORDER BY CreateDate LIMIT $3")
+                .bind(max_attempts)
+                .bind(now)
+                .bind(take as i64)
+                .map(|row: PgRow| {
+                    convert::row_to_outbox_message_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn mark_sent(&self, id: Uuid, sent_date: DateTime<Utc>) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("UPDATE EmailOutbox SET SentDate = $1, Attempts = Attempts + 1, LastError = NULL WHERE Id = $2")
+                .bind(sent_date)
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn mark_failed(&self, id: Uuid, next_attempt_date: DateTime<Utc>, error: &str) -> Result<(), Error> {
         let affected = 
-            sqlx::query("UPDATE Tasks SET Summary = $1, Description = $2, DueDate = $3, Priority = $4, Status = $5 WHERE Id = $6")
-                .bind(summary)
-                .bind(description)
-                .bind(due_date)
-                .bind(convert::priority_to_i16(priority))
-                .bind(convert::status_to_i16(status))
+            sqlx::query("UPDATE EmailOutbox SET Attempts = Attempts + 1, NextAttemptDate = $1, LastError = $2 WHERE Id = $3")
+                .bind(next_attempt_date)
+                .bind(error)
                 .bind(id)
                 .execute(&self.pool)
                 .await
                 .unwrap()
                 .rows_affected();
 
-        if affected > 0 { Ok(()) } else { Err(Error::EntityNotFound(id.to_string())) }
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+
+    async fn get_digest_items(&self, created_before: DateTime<Utc>) -> Vec<OutboxMessageEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM EmailOutbox WHERE SentDate IS NULL AND Digest AND CreateDate < $1 ORDER BY CreateDate")
+                .bind(created_before)
+                .map(|row: PgRow| {
+                    convert::row_to_outbox_message_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
     }
 }
 
 #[async_trait]
-impl LogRepository for LogStorage {
-    async fn insert(&self, entity: LogEntity) {
-        let _ = 
-            sqlx::query("INSERT INTO Logs (Id, Action, TimestampMsec, EntityId, EntityType, Payload) VALUES ($1, $2, $3, $4, $5, $6)")
-                .bind(entity.id)
-                .bind(convert::action_to_i16(entity.action))
-                .bind(entity.timestamp)
-                .bind(entity.entity_id)
-                .bind(entity.entity_type)
-                .bind(entity.payload)
-                .execute(&self.pool)
+impl WorkflowRepository for WorkflowStorage {
+    async fn get_transitions(&self, workspace_id: Uuid) -> Vec<WorkflowTransitionEntity> {
+        sqlx::query("SELECT * FROM WorkflowTransitions WHERE WorkspaceId = $1 ORDER BY FromStatusId, ToStatusId")
+            .bind(workspace_id)
+            .map(|row: PgRow| {
+                convert::row_to_workflow_transition_entity(&row)
+            })
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or(vec![])
+    }
+
+    async fn get_hooks(&self, workspace_id: Uuid) -> Vec<WorkflowHookEntity> {
+        sqlx::query("SELECT * FROM WorkflowHooks WHERE WorkspaceId = $1 ORDER BY StatusId, Hook")
+            .bind(workspace_id)
+            .map(|row: PgRow| {
+                convert::row_to_workflow_hook_entity(&row)
+            })
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or(vec![])
+    }
+
+    async fn replace(&self, workspace_id: Uuid, transitions: Vec<WorkflowTransitionEntity>, hooks: Vec<WorkflowHookEntity>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        for table in ["WorkflowTransitions", "WorkflowHooks"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE WorkspaceId = $1", table))
+                .bind(workspace_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        for entity in transitions {
+            sqlx::query("INSERT INTO WorkflowTransitions (WorkspaceId, FromStatusId, ToStatusId, RequiredFields) VALUES ($1, $2, $3, $4)")
+                .bind(entity.workspace_id)
+                .bind(entity.from_status_id)
+                .bind(entity.to_status_id)
+                .bind(entity.required_fields)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        for entity in hooks {
+            sqlx::query("INSERT INTO WorkflowHooks (WorkspaceId, StatusId, Hook) VALUES ($1, $2, $3)")
+                .bind(entity.workspace_id)
+                .bind(entity.status_id)
+                .bind(entity.hook)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+}
+
+const TEMPLATE_NODE_INSERT: &str = "INSERT INTO TaskTemplateNodes (Id, TemplateId, ParentId, Position, Summary, Description, Priority, DueInDays) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)";
+
+#[async_trait]
+impl TemplateRepository for TemplateStorage {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TemplateEntity, Error> {
+        let result = 
+            sqlx::query("SELECT * FROM TaskTemplates WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_template_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        if let Some(r) = result {
+            return Ok(r);
+        }
+
+        Err(Error::not_found(id))
+    }
+
+    async fn get_all(&self, workspace_id: Uuid) -> Vec<TemplateEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM TaskTemplates WHERE WorkspaceId = $1 ORDER BY Name")
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_template_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn get_nodes(&self, template_id: Uuid) -> Vec<TemplateNodeEntity> {
+        let result = 
+            sqlx::query("SELECT * FROM TaskTemplateNodes WHERE TemplateId = $1 ORDER BY Position")
+                .bind(template_id)
+                .map(|row: PgRow| {
+                    convert::row_to_template_node_entity(&row)
+                })
+                .fetch_all(&self.pool)
                 .await;
+
+        result.unwrap_or(vec![])
     }
 
-    async fn get_batch_by_entity_type(&self, entity_type: &str, continuation_token: &str, take: i32, descending: bool) -> (Vec<domain::models::LogEntity>, String) {
-        let skip = continuation_token.parse::<i32>().unwrap();
-        let sort = if descending { "DESC" } else { "ASC" };
+    async fn insert(&self, entity: TemplateEntity, nodes: Vec<TemplateNodeEntity>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        sqlx::query("INSERT INTO TaskTemplates (Id, WorkspaceId, Name, CreateDate) VALUES ($1, $2, $3, $4)")
+            .bind(entity.id)
+            .bind(entity.workspace_id)
+            .bind(entity.name)
+            .bind(entity.create_date)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        for node in nodes {
+            sqlx::query(TEMPLATE_NODE_INSERT)
+                .bind(node.id)
+                .bind(node.template_id)
+                .bind(node.parent_id)
+                .bind(node.position)
+                .bind(node.summary)
+                .bind(node.description)
+                .bind(node.priority)
+                .bind(node.due_in_days)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn update(&self, workspace_id: Uuid, id: Uuid, name: &str, nodes: Vec<TemplateNodeEntity>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        let affected = 
+            sqlx::query("UPDATE TaskTemplates SET Name = $1 WHERE Id = $2 AND WorkspaceId = $3")
+                .bind(name)
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?
+                .rows_affected();
+
+        if affected == 0 {
+            return Err(Error::not_found(id));
+        }
+
+        sqlx::query("DELETE FROM TaskTemplateNodes WHERE TemplateId = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        for node in nodes {
+            sqlx::query(TEMPLATE_NODE_INSERT)
+                .bind(node.id)
+                .bind(node.template_id)
+                .bind(node.parent_id)
+                .bind(node.position)
+                .bind(node.summary)
+                .bind(node.description)
+                .bind(node.priority)
+                .bind(node.due_in_days)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn delete(&self, workspace_id: Uuid, id: Uuid) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM TaskTemplates WHERE Id = $1 AND WorkspaceId = $2")
+                .bind(id)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .unwrap()
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(id)) }
+    }
+}
+
+#[async_trait]
+impl TrashRepository for TrashStorage {
+    async fn get_by_id(&self, workspace_id: Uuid, id: Uuid) -> Result<TrashItemEntity, Error> {
+        let result = 
+            sqlx::query(&format!("{} AND t.Id = $1 AND t.WorkspaceId = $2", TRASH_SELECT))
+                .bind(id)
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    convert::row_to_trash_item_entity(&row)
+                })
+                .fetch_optional(&self.pool)
+                .await
+                .unwrap();
+
+        result.ok_or(Error::not_found(id))
+    }
 
+    async fn get_batch(&self, workspace_id: Uuid, take: i32, continuation_token: &str) -> (Vec<TrashItemEntity>, String) {
+        let skip = continuation_token.parse::<i32>().unwrap();
         let entities = 
-            sqlx::query(format!("SELECT * FROM Logs WHERE EntityType = $1 ORDER BY TimestampMsec {} LIMIT $3 OFFSET $4", sort).as_str())
-                .bind(entity_type)
-                .bind(sort)
+            sqlx::query(&format!("{} AND t.WorkspaceId = $1 AND {} ORDER BY t.DeletedAt DESC, t.Id LIMIT $2 OFFSET $3", TRASH_SELECT, TRASH_TOP_LEVEL))
+                .bind(workspace_id)
                 .bind(take)
                 .bind(skip)
                 .map(|row: PgRow| {
-                    convert::row_to_log_entity(&row)
+                    convert::row_to_trash_item_entity(&row)
                 })
                 .fetch_all(&self.pool)
                 .await
-                .unwrap();
+                .unwrap_or(vec![]);
 
         let skip = if {entities.len() as i32} < take { skip + entities.len() as i32 } else { skip + take };
 
         (entities, skip.to_string())
     }
 
-    async fn get_batch_by_entity(&self, entity_id: Uuid, continuation_token: &str, take: i32, descending: bool) -> (Vec<domain::models::LogEntity>, String) {
-        let skip = continuation_token.parse::<i32>().unwrap();
-        let sort = if descending { "DESC" } else { "ASC" };
+    async fn get_subtree(&self, workspace_id: Uuid, id: Uuid) -> Vec<Uuid> {
+        let result = sqlx::query(&format!("{} select trashed.Id as val from trashed;", TRASHED_CTE))
+            .bind(id)
+            .bind(workspace_id)
+            .map(|row: PgRow| {
+                row.get::<Uuid, _>("val")
+            })
+            .fetch_all(&self.pool)
+            .await;
 
-        let entities = 
-            sqlx::query("SELECT * FROM Logs WHERE EntityId = $1 ORDER BY TimestampMsec $2 LIMIT $3 OFFSET $4")
-                .bind(entity_id)
-                .bind(sort)
-                .bind(take)
-                .bind(skip)
+        result.unwrap_or(vec![])
+    }
+
+    async fn get_expired(&self, deleted_before: DateTime<Utc>) -> Vec<TrashItemEntity> {
+        let result = 
+            sqlx::query(&format!("{} AND t.DeletedAt <= $1 AND {} ORDER BY t.DeletedAt", TRASH_SELECT, TRASH_TOP_LEVEL))
+                .bind(deleted_before)
                 .map(|row: PgRow| {
-                    convert::row_to_log_entity(&row)
+                    convert::row_to_trash_item_entity(&row)
+                })
+                .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn get_detached(&self, workspace_id: Uuid, id: Uuid) -> Vec<Uuid> {
+        let result = 
+            sqlx::query("SELECT Id FROM Tasks WHERE TrashedParentId = $1 AND WorkspaceId = $2 AND DeletedAt IS NULL")
+                .bind(id)
+                .bind(workspace_id)
+                .map(|row: PgRow| {
+                    row.get::<Uuid, _>("id")
                 })
                 .fetch_all(&self.pool)
+                .await;
+
+        result.unwrap_or(vec![])
+    }
+
+    async fn restore(&self, workspace_id: Uuid, ids: &[Uuid], root_task_id: Option<Uuid>, reattached: &[Uuid]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await.map_err(|e| Error::DbError(e.to_string()))?;
+
+        let affected = 
+            sqlx::query("UPDATE Tasks SET Version = Version + 1, DeletedAt = NULL, DeletedBy = NULL WHERE Id = ANY($1) AND WorkspaceId = $2 AND DeletedAt IS NOT NULL")
+                .bind(ids)
+                .bind(workspace_id)
+                .execute(&mut *tx)
                 .await
-                .unwrap();
+                .map_err(|e| Error::DbError(e.to_string()))?
+                .rows_affected();
 
-        let skip = if {entities.len() as i32} < take { skip + entities.len() as i32 } else { skip + take };
+        if affected == 0 {
+            return Err(Error::not_found(ids[0]));
+        }
 
-        (entities, skip.to_string())
+        sqlx::query("UPDATE Tasks SET Version = Version + 1, RootTaskId = $1 WHERE Id = $2 AND WorkspaceId = $3")
+            .bind(root_task_id)
+            .bind(ids[0])
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        sqlx::query("UPDATE Tasks SET Version = Version + 1, RootTaskId = $1, TrashedParentId = NULL WHERE Id = ANY($2) AND TrashedParentId = $1 AND WorkspaceId = $3 AND DeletedAt IS NULL")
+            .bind(ids[0])
+            .bind(reattached)
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| Error::DbError(e.to_string()))
+    }
+
+    async fn delete_many(&self, workspace_id: Uuid, ids: &[Uuid]) -> Result<(), Error> {
+        let affected = 
+            sqlx::query("DELETE FROM Tasks WHERE Id = ANY($1) AND WorkspaceId = $2 AND DeletedAt IS NOT NULL")
+                .bind(ids)
+                .bind(workspace_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?
+                .rows_affected();
+
+        if affected > 0 { Ok(()) } else { Err(Error::not_found(ids[0])) }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for IdempotencyStorage {
+    async fn reserve(&self, workspace_id: Uuid, key: &str, fingerprint: &str, now: DateTime<Utc>, expires_at: DateTime<Utc>) -> Result<bool, Error> {
+        // An expired key is taken over as if it was never used
+        let affected =
+            sqlx::query("INSERT INTO IdempotencyKeys (WorkspaceId, Key, Fingerprint, CreateDate, ExpiresAt) VALUES ($1, $2, $3, $4, $5) \
+                ON CONFLICT (WorkspaceId, Key) DO UPDATE SET Fingerprint = EXCLUDED.Fingerprint, StatusCode = NULL, ContentType = NULL, Body = NULL, CreateDate = EXCLUDED.CreateDate, ExpiresAt = EXCLUDED.ExpiresAt \
+                WHERE IdempotencyKeys.ExpiresAt <= $4")
+                .bind(workspace_id)
+                .bind(key)
+                .bind(fingerprint)
+                .bind(now)
+                .bind(expires_at)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| Error::DbError(e.to_string()))?
+                .rows_affected();
+
+        Ok(affected > 0)
+    }
+
+    async fn get_by_key(&self, workspace_id: Uuid, key: &str) -> Option<IdempotencyKeyEntity> {
+        sqlx::query("SELECT * FROM IdempotencyKeys WHERE WorkspaceId = $1 AND Key = $2")
+            .bind(workspace_id)
+            .bind(key)
+            .map(|row: PgRow| {
+                convert::row_to_idempotency_key_entity(&row)
+            })
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap_or(None)
+    }
+
+    async fn complete(&self, workspace_id: Uuid, key: &str, status_code: i16, content_type: Option<&str>, body: &[u8]) -> Result<(), Error> {
+        sqlx::query("UPDATE IdempotencyKeys SET StatusCode = $1, ContentType = $2, Body = $3 WHERE WorkspaceId = $4 AND Key = $5")
+            .bind(status_code)
+            .bind(content_type)
+            .bind(body)
+            .bind(workspace_id)
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, workspace_id: Uuid, key: &str) -> Result<(), Error> {
+        sqlx::query("DELETE FROM IdempotencyKeys WHERE WorkspaceId = $1 AND Key = $2")
+            .bind(workspace_id)
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::DbError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> u64 {
+        sqlx::query("DELETE FROM IdempotencyKeys WHERE ExpiresAt <= $1")
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected())
+            .unwrap_or(0)
     }
-}
\ No newline at end of file
+}
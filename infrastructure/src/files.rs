@@ -0,0 +1,60 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use app::{repos::FileStorage, errors::Error};
+
+use async_trait::async_trait;
+use tokio::fs;
+use uuid::Uuid;
+
+// Keeps files on local disk as <root>/<first two hash chars>/<hash>
+pub struct LocalFileStorage {
+    root: PathBuf
+}
+
+impl LocalFileStorage {
+    pub fn new(root: &str) -> LocalFileStorage {
+        LocalFileStorage { root: PathBuf::from(root) }
+    }
+
+    fn path(&self, hash: &str) -> Result<PathBuf, Error> {
+        // Hashes come from our own database, but the check keeps paths inside the root anyway
+        if hash.len() < 3 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::invalid_input("Invalid file hash"));
+        }
+
+        Ok(self.root.join(&hash[..2]).join(hash))
+    }
+}
+
+#[async_trait]
+impl FileStorage for LocalFileStorage {
+    async fn save(&self, hash: &str, content: &[u8]) -> Result<(), Error> {
+        let path = self.path(hash)?;
+        if fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(());
+        }
+
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir).await.map_err(|e| Error::IoError(e.to_string()))?;
+
+        // Write to a temporary file first, so a half-written file never appears under its hash
+        let tmp_path = dir.join(format!("{}.tmp", Uuid::new_v4()));
+        fs::write(&tmp_path, content).await.map_err(|e| Error::IoError(e.to_string()))?;
+        fs::rename(&tmp_path, &path).await.map_err(|e| Error::IoError(e.to_string()))
+    }
+
+    async fn load(&self, hash: &str) -> Result<Vec<u8>, Error> {
+        fs::read(self.path(hash)?).await.map_err(|e| match e.kind() {
+            ErrorKind::NotFound => Error::EntityNotFound(hash.to_string()),
+            _ => Error::IoError(e.to_string())
+        })
+    }
+
+    async fn remove(&self, hash: &str) -> Result<(), Error> {
+        match fs::remove_file(self.path(hash)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(Error::IoError(e.to_string()))
+        }
+    }
+}
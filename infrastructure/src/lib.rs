@@ -1,19 +1,43 @@
-use app::{tasks::TaskService, logs::LogService};
-use db::{LogStorage, TaskStorage };
+use app::{tasks::TaskService, logs::LogService, workspaces::WorkspaceService, tags::TagService, comments::CommentService, attachments::AttachmentService, checklists::ChecklistService, due_dates::DueDateService, recurrence::RecurrenceService, reminders::ReminderService, notifications::NotificationService, workflow::WorkflowService, statuses::StatusService, trees::TreeService, templates::TemplateService, trash::TrashService, archive::ArchiveService, idempotency::IdempotencyService, repos::{ReminderChannel, Mailer}};
+use db::{LogStorage, TaskStorage, WorkspaceStorage, TagStorage, CommentStorage, AttachmentStorage, ChecklistStorage, DependencyStorage, RecurrenceStorage, ReminderStorage, NotificationStorage, WorkflowStorage, StatusStorage, PriorityStorage, TemplateStorage, TrashStorage, IdempotencyStorage};
+use channels::{FeedChannel, WebhookChannel, EmailChannel};
+use files::LocalFileStorage;
+use mail::SmtpMailer;
+use settings::Settings;
 use sqlx::postgres::PgPoolOptions;
 
 use std::{time::Duration, sync::Arc};
 
 pub mod db;
 pub mod convert;
+pub mod files;
+pub mod settings;
+pub mod channels;
+pub mod mail;
 
 pub struct ServiceProvider {
     task_service: Arc<TaskService>,
-    log_service: Arc<LogService>
+    log_service: Arc<LogService>,
+    workspace_service: Arc<WorkspaceService>,
+    tag_service: Arc<TagService>,
+    comment_service: Arc<CommentService>,
+    attachment_service: Arc<AttachmentService>,
+    checklist_service: Arc<ChecklistService>,
+    due_date_service: Arc<DueDateService>,
+    recurrence_service: Arc<RecurrenceService>,
+    reminder_service: Arc<ReminderService>,
+    notification_service: Arc<NotificationService>,
+    workflow_service: Arc<WorkflowService>,
+    status_service: Arc<StatusService>,
+    tree_service: Arc<TreeService>,
+    template_service: Arc<TemplateService>,
+    trash_service: Arc<TrashService>,
+    archive_service: Arc<ArchiveService>,
+    idempotency_service: Arc<IdempotencyService>
 }
 
 impl ServiceProvider {
-    pub fn new(connection_string: &str) -> ServiceProvider {
+    pub fn new(connection_string: &str, settings: &Settings) -> ServiceProvider {
         let pool = PgPoolOptions::new()
             .max_connections(10)
             .acquire_timeout(Duration::from_secs(3))
@@ -23,9 +47,62 @@ impl ServiceProvider {
         // Arc<T> is a thread-safe reference count pointer, actually when clone() called it just passing the same pointer, but increasing ref count
         // Exactly what we need here
         let log_ervice_ptr: Arc<LogService> = Arc::new(LogService::new(Arc::new(LogStorage::new(pool.clone()))));
+        let workspace_storage_ptr = Arc::new(WorkspaceStorage::new(pool.clone()));
+        let tag_storage_ptr = Arc::new(TagStorage::new(pool.clone()));
+        let task_storage_ptr = Arc::new(TaskStorage::new(pool.clone()));
+        let attachment_service_ptr = Arc::new(AttachmentService::new(
+            Arc::new(AttachmentStorage::new(pool.clone())),
+            task_storage_ptr.clone(),
+            Arc::new(LocalFileStorage::new(&settings.attachments_dir)),
+            Arc::clone(&log_ervice_ptr),
+            settings.attachment_max_size));
+
+        let status_service_ptr = Arc::new(StatusService::new(Arc::new(StatusStorage::new(pool.clone())), Arc::new(PriorityStorage::new(pool.clone())), Arc::clone(&log_ervice_ptr)));
+        let checklist_storage_ptr = Arc::new(ChecklistStorage::new(pool.clone()));
+        let recurrence_service_ptr = Arc::new(RecurrenceService::new(Arc::new(RecurrenceStorage::new(pool.clone())), task_storage_ptr.clone(), status_service_ptr.clone(), Arc::clone(&log_ervice_ptr)));
+
+        let mailer_ptr: Arc<dyn Mailer> = Arc::new(SmtpMailer::new(&settings.smtp_host, settings.smtp_port, &settings.mail_from));
+        let notification_service_ptr = Arc::new(NotificationService::new(
+            Arc::new(NotificationStorage::new(pool.clone())),
+            task_storage_ptr.clone(),
+            mailer_ptr.clone(),
+            settings.notification_max_attempts,
+            settings.digest_hour));
+
+        let workflow_service_ptr = Arc::new(WorkflowService::new(Arc::new(WorkflowStorage::new(pool.clone())), workspace_storage_ptr.clone(), status_service_ptr.clone(), Arc::clone(&log_ervice_ptr)));
+
+        let reminder_channels: Vec<Arc<dyn ReminderChannel>> = settings.reminder_channels.iter().map(|name| -> Arc<dyn ReminderChannel> {
+            match name.as_str() {
+                "feed" => Arc::new(FeedChannel::new(Arc::clone(&log_ervice_ptr))),
+                "webhook" => Arc::new(WebhookChannel::new(settings.reminder_webhook_url.as_deref().expect("REMINDER_WEBHOOK_URL must be set for the webhook channel"))),
+                "notify" => notification_service_ptr.clone(),
+                "email" => Arc::new(EmailChannel::new(mailer_ptr.clone(), settings.reminder_email_to.as_deref().expect("REMINDER_EMAIL_TO must be set for the email channel"))),
+                _ => panic!("unknown reminder channel {}", name)
+            }
+        }).collect();
+
+        let task_service_ptr = Arc::new(TaskService::new(task_storage_ptr.clone(), workspace_storage_ptr.clone(), tag_storage_ptr.clone(), checklist_storage_ptr.clone(), Arc::new(DependencyStorage::new(pool.clone())), Arc::clone(&log_ervice_ptr), recurrence_service_ptr.clone(), notification_service_ptr.clone(), workflow_service_ptr.clone(), status_service_ptr.clone(), settings.enforce_blockers, settings.require_if_match));
+
+        let template_service_ptr = Arc::new(TemplateService::new(Arc::new(TemplateStorage::new(pool.clone())), workspace_storage_ptr.clone(), task_service_ptr.clone(), status_service_ptr.clone(), Arc::clone(&log_ervice_ptr)));
 
         ServiceProvider { 
-            task_service: Arc::new(TaskService::new(Arc::new(TaskStorage::new(pool.clone())), Arc::clone(&log_ervice_ptr))),
+            reminder_service: Arc::new(ReminderService::new(Arc::new(ReminderStorage::new(pool.clone())), task_storage_ptr.clone(), reminder_channels, Arc::clone(&log_ervice_ptr), &settings.reminder_offsets)),
+            task_service: task_service_ptr,
+            template_service: template_service_ptr,
+            archive_service: Arc::new(ArchiveService::new(task_storage_ptr.clone(), Arc::clone(&log_ervice_ptr), settings.auto_archive_days)),
+            idempotency_service: Arc::new(IdempotencyService::new(Arc::new(IdempotencyStorage::new(pool.clone())), settings.idempotency_ttl_secs)),
+            trash_service: Arc::new(TrashService::new(Arc::new(TrashStorage::new(pool.clone())), task_storage_ptr.clone(), attachment_service_ptr.clone(), Arc::clone(&log_ervice_ptr), settings.trash_retention_days)),
+            workflow_service: workflow_service_ptr,
+            recurrence_service: recurrence_service_ptr,
+            due_date_service: Arc::new(DueDateService::new(task_storage_ptr.clone(), Arc::clone(&log_ervice_ptr))),
+            checklist_service: Arc::new(ChecklistService::new(checklist_storage_ptr, task_storage_ptr.clone(), Arc::clone(&log_ervice_ptr))),
+            attachment_service: attachment_service_ptr.clone(),
+            tree_service: Arc::new(TreeService::new(task_storage_ptr.clone(), status_service_ptr.clone())),
+            comment_service: Arc::new(CommentService::new(Arc::new(CommentStorage::new(pool.clone())), task_storage_ptr, Arc::clone(&log_ervice_ptr), notification_service_ptr.clone())),
+            notification_service: notification_service_ptr,
+            workspace_service: Arc::new(WorkspaceService::new(workspace_storage_ptr, status_service_ptr.clone(), attachment_service_ptr, Arc::clone(&log_ervice_ptr))),
+            status_service: status_service_ptr,
+            tag_service: Arc::new(TagService::new(tag_storage_ptr, Arc::clone(&log_ervice_ptr))),
             log_service: log_ervice_ptr
         }
     }
@@ -37,4 +114,68 @@ impl ServiceProvider {
     pub fn log_service(&self) -> Arc<LogService> {
         self.log_service.clone()
     }
-}
\ No newline at end of file
+
+    pub fn workspace_service(&self) -> Arc<WorkspaceService> {
+        self.workspace_service.clone()
+    }
+
+    pub fn tag_service(&self) -> Arc<TagService> {
+        self.tag_service.clone()
+    }
+
+    pub fn comment_service(&self) -> Arc<CommentService> {
+        self.comment_service.clone()
+    }
+
+    pub fn attachment_service(&self) -> Arc<AttachmentService> {
+        self.attachment_service.clone()
+    }
+
+    pub fn checklist_service(&self) -> Arc<ChecklistService> {
+        self.checklist_service.clone()
+    }
+
+    pub fn due_date_service(&self) -> Arc<DueDateService> {
+        self.due_date_service.clone()
+    }
+
+    pub fn recurrence_service(&self) -> Arc<RecurrenceService> {
+        self.recurrence_service.clone()
+    }
+
+    pub fn reminder_service(&self) -> Arc<ReminderService> {
+        self.reminder_service.clone()
+    }
+
+    pub fn notification_service(&self) -> Arc<NotificationService> {
+        self.notification_service.clone()
+    }
+
+    pub fn workflow_service(&self) -> Arc<WorkflowService> {
+        self.workflow_service.clone()
+    }
+
+    pub fn status_service(&self) -> Arc<StatusService> {
+        self.status_service.clone()
+    }
+
+    pub fn tree_service(&self) -> Arc<TreeService> {
+        self.tree_service.clone()
+    }
+
+    pub fn template_service(&self) -> Arc<TemplateService> {
+        self.template_service.clone()
+    }
+
+    pub fn trash_service(&self) -> Arc<TrashService> {
+        self.trash_service.clone()
+    }
+
+    pub fn archive_service(&self) -> Arc<ArchiveService> {
+        self.archive_service.clone()
+    }
+
+    pub fn idempotency_service(&self) -> Arc<IdempotencyService> {
+        self.idempotency_service.clone()
+    }
+}
@@ -0,0 +1,37 @@
+use app::{repos::Mailer, errors::Error};
+
+use async_trait::async_trait;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor, message::MultiPart};
+
+// Plain SMTP without auth or TLS, meant for a local relay such as MailHog
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: String
+}
+
+impl SmtpMailer {
+    pub fn new(host: &str, port: u16, from: &str) -> SmtpMailer {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host).port(port).build();
+
+        SmtpMailer { transport, from: from.to_string() }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, to: &str, subject: &str, text_body: &str, html_body: Option<&str>) -> Result<(), Error> {
+        let builder = Message::builder()
+            .from(self.from.parse().map_err(|_| Error::invalid_input("Invalid sender address"))?)
+            .to(to.parse().map_err(|_| Error::invalid_input("Invalid recipient address"))?)
+            .subject(subject);
+
+        let message = match html_body {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(text_body.to_string(), html.to_string())),
+            None => builder.body(text_body.to_string())
+        }.map_err(|e| Error::IoError(e.to_string()))?;
+
+        self.transport.send(message).await.map_err(|e| Error::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+}
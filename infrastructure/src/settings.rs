@@ -0,0 +1,66 @@
+use std::env;
+
+// Runtime options, every value can be overridden via environment variables
+pub struct Settings {
+    pub attachments_dir: String,
+    pub attachment_max_size: usize,
+    pub enforce_blockers: bool,
+    pub require_if_match: bool, // writes of a task without `If-Match` get 428
+    pub reminder_interval_secs: u64,
+    pub reminder_offsets: Vec<i64>, // minutes before the due date
+    pub reminder_channels: Vec<String>,
+    pub reminder_webhook_url: Option<String>,
+    pub reminder_email_to: Option<String>,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub mail_from: String,
+    pub notification_interval_secs: u64,
+    pub notification_max_attempts: i32,
+    pub digest_hour: u32, // UTC
+    pub trash_retention_days: i64, // 0 keeps trashed tasks until purged by hand
+    pub trash_purge_interval_secs: u64,
+    pub auto_archive_days: i64, // 0 leaves archiving to the users
+    pub archive_interval_secs: u64,
+    pub idempotency_ttl_secs: i64,
+    pub idempotency_purge_interval_secs: u64,
+}
+
+impl Settings {
+    pub fn from_env() -> Settings {
+        Settings {
+            attachments_dir: env::var("ATTACHMENTS_DIR").unwrap_or("./attachments".to_string()),
+            attachment_max_size: parse_or("ATTACHMENTS_MAX_SIZE", 10 * 1024 * 1024),
+            enforce_blockers: parse_or("ENFORCE_BLOCKERS", true),
+            require_if_match: parse_or("REQUIRE_IF_MATCH", false),
+            reminder_interval_secs: parse_or("REMINDER_INTERVAL_SECS", 60),
+            reminder_offsets: parse_list("REMINDER_OFFSETS", "1440,60"),
+            reminder_channels: parse_list("REMINDER_CHANNELS", "feed"),
+            reminder_webhook_url: env::var("REMINDER_WEBHOOK_URL").ok(),
+            reminder_email_to: env::var("REMINDER_EMAIL_TO").ok(),
+            smtp_host: env::var("SMTP_HOST").unwrap_or("localhost".to_string()),
+            smtp_port: parse_or("SMTP_PORT", 1025),
+            mail_from: env::var("MAIL_FROM").unwrap_or("todolist@localhost".to_string()),
+            notification_interval_secs: parse_or("NOTIFICATION_INTERVAL_SECS", 30),
+            notification_max_attempts: parse_or("NOTIFICATION_MAX_ATTEMPTS", 5),
+            digest_hour: parse_or("DIGEST_HOUR", 8),
+            trash_retention_days: parse_or("TRASH_RETENTION_DAYS", 30),
+            trash_purge_interval_secs: parse_or("TRASH_PURGE_INTERVAL_SECS", 3600),
+            auto_archive_days: parse_or("AUTO_ARCHIVE_DAYS", 0),
+            archive_interval_secs: parse_or("ARCHIVE_INTERVAL_SECS", 3600),
+            idempotency_ttl_secs: parse_or("IDEMPOTENCY_TTL_SECS", 24 * 3600),
+            idempotency_purge_interval_secs: parse_or("IDEMPOTENCY_PURGE_INTERVAL_SECS", 3600),
+        }
+    }
+}
+
+fn parse_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key).ok().and_then(|v| v.parse::<T>().ok()).unwrap_or(default)
+}
+
+// Comma separated values, unparsable items are skipped
+fn parse_list<T: std::str::FromStr>(key: &str, default: &str) -> Vec<T> {
+    env::var(key).unwrap_or(default.to_string())
+        .split(',')
+        .filter_map(|v| v.trim().parse::<T>().ok())
+        .collect()
+}
@@ -0,0 +1,86 @@
+// Runs against the database in TEST_DATABASE_URL (set up from init.sql) and does nothing when it isn't set
+use app::{ranks, repos::{TaskRepository, TaskChanges, TaskWrite}};
+use chrono::Utc;
+use infrastructure::db::TaskStorage;
+use sqlx::{postgres::PgPool, Row};
+use uuid::Uuid;
+
+const WORKSPACE_ID: Uuid = Uuid::from_u128(1);
+
+async fn connect() -> Option<PgPool> {
+    let url = std::env::var("TEST_DATABASE_URL").ok()?;
+    Some(PgPool::connect(&url).await.unwrap())
+}
+
+async fn insert_task(storage: &TaskStorage, pool: &PgPool, summary: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let last_rank = storage.get_last_rank(WORKSPACE_ID, None).await;
+
+    sqlx::query("INSERT INTO Tasks (Id, WorkspaceId, Summary, Description, CreateDate, DueDate, PriorityId, StatusId, Rank) \
+                 SELECT $1, $2, $3, '', NOW(), NOW() + INTERVAL '1 day', (SELECT Id FROM Priorities WHERE WorkspaceId = $2 LIMIT 1), (SELECT Id FROM Statuses WHERE WorkspaceId = $2 LIMIT 1), $4")
+        .bind(id)
+        .bind(WORKSPACE_ID)
+        .bind(summary)
+        .bind(ranks::between(last_rank.as_deref(), None))
+        .execute(pool)
+        .await
+        .unwrap();
+
+    id
+}
+
+async fn delete_task(pool: &PgPool, id: Uuid) {
+    sqlx::query("DELETE FROM Tasks WHERE Id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn get_state(pool: &PgPool, id: Uuid) -> (String, bool) {
+    let row =
+        sqlx::query("SELECT Summary, DeletedAt IS NULL AS Alive FROM Tasks WHERE Id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+
+    (row.get("summary"), row.get("alive"))
+}
+
+fn rename(id: Uuid, summary: &str) -> TaskWrite {
+    TaskWrite::Update { id, changes: TaskChanges { summary: Some(summary.to_string()), ..Default::default() } }
+}
+
+#[tokio::test]
+async fn write_all_rolls_back_when_second_write_fails() {
+    let Some(pool) = connect().await else { return; };
+    let storage = TaskStorage::new(pool.clone());
+    let id = insert_task(&storage, &pool, "before").await;
+
+    let writes = vec![rename(id, "after"), rename(Uuid::new_v4(), "missing")];
+    let result = storage.write_all(WORKSPACE_ID, &writes, Utc::now(), None).await;
+
+    let state = get_state(&pool, id).await;
+    delete_task(&pool, id).await;
+
+    assert!(matches!(result, Err((1, _))));
+    assert_eq!(state, ("before".to_string(), true));
+}
+
+#[tokio::test]
+async fn write_all_sees_earlier_writes_of_the_same_request() {
+    let Some(pool) = connect().await else { return; };
+    let storage = TaskStorage::new(pool.clone());
+    let id = insert_task(&storage, &pool, "before").await;
+
+    // The update can't find the task trashed just before it, so the trashing is undone as well
+    let writes = vec![TaskWrite::Trash { ids: vec![id], expected_version: None }, rename(id, "after")];
+    let result = storage.write_all(WORKSPACE_ID, &writes, Utc::now(), None).await;
+
+    let state = get_state(&pool, id).await;
+    delete_task(&pool, id).await;
+
+    assert!(matches!(result, Err((1, _))));
+    assert_eq!(state, ("before".to_string(), true));
+}
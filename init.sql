@@ -2,17 +2,248 @@ CREATE DATABASE todolist;
 
 \c todolist;
 
+CREATE TABLE IF NOT EXISTS Workspaces (
+    Id UUID PRIMARY KEY NOT NULL,
+    Name VARCHAR(255) NOT NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    CONSTRAINT Id_UNIQUE_W UNIQUE (Id)
+);
+
+INSERT INTO Workspaces (Id, Name) VALUES ('00000000-0000-0000-0000-000000000001', 'Default');
+
+CREATE TABLE IF NOT EXISTS Statuses (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(64) NOT NULL,
+    Color VARCHAR(7) NOT NULL,
+    Position INTEGER NOT NULL DEFAULT 0,
+    Category SMALLINT NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_S UNIQUE (Id),
+    CONSTRAINT STATUS_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS Priorities (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(64) NOT NULL,
+    Color VARCHAR(7) NOT NULL,
+    Position INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_P UNIQUE (Id),
+    CONSTRAINT PRIORITY_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+-- Category: 0 todo, 1 in progress, 2 done
+INSERT INTO Statuses (Id, WorkspaceId, Name, Color, Position, Category) VALUES
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Reserved', '#9e9e9e', 0, 0),
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Ongoing', '#1e90ff', 1, 1),
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Pending', '#f9a825', 2, 0),
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Done', '#2e7d32', 3, 2);
+
+INSERT INTO Priorities (Id, WorkspaceId, Name, Color, Position) VALUES
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Low', '#8bc34a', 0),
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Normal', '#1e90ff', 1),
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'High', '#ff9800', 2),
+    (gen_random_uuid(), '00000000-0000-0000-0000-000000000001', 'Urgent', '#f44336', 3);
+
 CREATE TABLE IF NOT EXISTS Tasks (
     Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
     RootTaskId UUID NULL,
     Summary VARCHAR(255) NOT NULL,
     Description TEXT NOT NULL,
     CreateDate TIMESTAMPTZ DEFAULT NOW(),
     DueDate TIMESTAMPTZ,
-    Priority SMALLINT NOT NULL DEFAULT 0,
-    Status SMALLINT NOT NULL DEFAULT 0,
+    Assignee VARCHAR(320) NULL,
+    Resolution TEXT NULL,
+    PriorityId UUID NOT NULL,
+    StatusId UUID NOT NULL,
+    AutoComplete BOOLEAN NOT NULL DEFAULT FALSE,
+    DeletedAt TIMESTAMPTZ NULL,
+    DeletedBy VARCHAR(320) NULL,
+    ArchivedAt TIMESTAMPTZ NULL,
+    DoneDate TIMESTAMPTZ NULL,
+    Rank VARCHAR(255) COLLATE "C" NOT NULL DEFAULT '',
+    Version INTEGER NOT NULL DEFAULT 1,
+    TrashedParentId UUID NULL,
     CONSTRAINT Id_UNIQUE_T UNIQUE (Id),
-    CONSTRAINT ROOT_TASK_ID_KEY FOREIGN KEY (RootTaskId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION
+    CONSTRAINT ROOT_TASK_ID_KEY FOREIGN KEY (RootTaskId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION,
+    CONSTRAINT TRASHED_PARENT_ID_KEY FOREIGN KEY (TrashedParentId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION,
+    CONSTRAINT WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT STATUS_ID_KEY FOREIGN KEY (StatusId) REFERENCES Statuses (Id) ON DELETE NO ACTION ON UPDATE NO ACTION,
+    CONSTRAINT PRIORITY_ID_KEY FOREIGN KEY (PriorityId) REFERENCES Priorities (Id) ON DELETE NO ACTION ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS Tags (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(64) NOT NULL,
+    Color VARCHAR(7) NOT NULL,
+    CONSTRAINT Id_UNIQUE_TG UNIQUE (Id),
+    CONSTRAINT NAME_UNIQUE_TG UNIQUE (WorkspaceId, Name),
+    CONSTRAINT TAG_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS TaskTags (
+    TaskId UUID NOT NULL,
+    TagId UUID NOT NULL,
+    PRIMARY KEY (TaskId, TagId),
+    CONSTRAINT TASK_TAGS_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT TASK_TAGS_TAG_ID_KEY FOREIGN KEY (TagId) REFERENCES Tags (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS Comments (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    Author VARCHAR(255) NULL,
+    Body TEXT NOT NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    UpdateDate TIMESTAMPTZ NULL,
+    CONSTRAINT Id_UNIQUE_C UNIQUE (Id),
+    CONSTRAINT COMMENT_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS CommentRevisions (
+    Id UUID PRIMARY KEY NOT NULL,
+    CommentId UUID NOT NULL,
+    Body TEXT NOT NULL,
+    CreateDate TIMESTAMPTZ NOT NULL,
+    CONSTRAINT Id_UNIQUE_CR UNIQUE (Id),
+    CONSTRAINT REVISION_COMMENT_ID_KEY FOREIGN KEY (CommentId) REFERENCES Comments (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS Attachments (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    FileName VARCHAR(255) NOT NULL,
+    ContentType VARCHAR(255) NOT NULL,
+    Size BIGINT NOT NULL,
+    Hash CHAR(64) NOT NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    CONSTRAINT Id_UNIQUE_A UNIQUE (Id),
+    CONSTRAINT ATTACHMENT_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS ChecklistItems (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    Text VARCHAR(1024) NOT NULL,
+    Done BOOLEAN NOT NULL DEFAULT FALSE,
+    Position INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_CI UNIQUE (Id),
+    CONSTRAINT CHECKLIST_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS TaskDependencies (
+    BlockerId UUID NOT NULL,
+    BlockedId UUID NOT NULL,
+    PRIMARY KEY (BlockerId, BlockedId),
+    CONSTRAINT NO_SELF_DEPENDENCY CHECK (BlockerId <> BlockedId),
+    CONSTRAINT DEPENDENCY_BLOCKER_ID_KEY FOREIGN KEY (BlockerId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT DEPENDENCY_BLOCKED_ID_KEY FOREIGN KEY (BlockedId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS TaskRecurrences (
+    TaskId UUID PRIMARY KEY NOT NULL,
+    Rule VARCHAR(256) NOT NULL,
+    CloneSubtasks BOOLEAN NOT NULL DEFAULT FALSE,
+    Occurrence INTEGER NOT NULL DEFAULT 1,
+    PreviousTaskId UUID NULL,
+    NextTaskId UUID NULL,
+    CONSTRAINT RECURRENCE_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT RECURRENCE_PREVIOUS_TASK_ID_KEY FOREIGN KEY (PreviousTaskId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION,
+    CONSTRAINT RECURRENCE_NEXT_TASK_ID_KEY FOREIGN KEY (NextTaskId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS FiredReminders (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    Kind VARCHAR(64) NOT NULL,
+    DueDate TIMESTAMPTZ NOT NULL,
+    FireDate TIMESTAMPTZ NOT NULL,
+    CONSTRAINT Id_UNIQUE_FR UNIQUE (Id),
+    CONSTRAINT FIRED_REMINDER_UNIQUE UNIQUE (TaskId, Kind, DueDate),
+    CONSTRAINT REMINDER_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS NotificationPreferences (
+    Email VARCHAR(320) PRIMARY KEY NOT NULL,
+    Assignment BOOLEAN NOT NULL DEFAULT TRUE,
+    StatusChange BOOLEAN NOT NULL DEFAULT TRUE,
+    Comments BOOLEAN NOT NULL DEFAULT TRUE,
+    DueSoon BOOLEAN NOT NULL DEFAULT TRUE,
+    Digest BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE IF NOT EXISTS EmailOutbox (
+    Id UUID PRIMARY KEY NOT NULL,
+    Recipient VARCHAR(320) NOT NULL,
+    Subject VARCHAR(512) NOT NULL,
+    TextBody TEXT NOT NULL,
+    HtmlBody TEXT NOT NULL,
+    Digest BOOLEAN NOT NULL DEFAULT FALSE,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    Attempts INTEGER NOT NULL DEFAULT 0,
+    NextAttemptDate TIMESTAMPTZ DEFAULT NOW(),
+    SentDate TIMESTAMPTZ NULL,
+    LastError TEXT NULL,
+    CONSTRAINT Id_UNIQUE_EO UNIQUE (Id)
+);
+
+CREATE TABLE IF NOT EXISTS WorkflowTransitions (
+    WorkspaceId UUID NOT NULL,
+    FromStatusId UUID NOT NULL,
+    ToStatusId UUID NOT NULL,
+    RequiredFields VARCHAR(64)[] NOT NULL DEFAULT '{}',
+    PRIMARY KEY (WorkspaceId, FromStatusId, ToStatusId),
+    CONSTRAINT NO_SELF_TRANSITION CHECK (FromStatusId <> ToStatusId),
+    CONSTRAINT TRANSITION_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT TRANSITION_FROM_STATUS_ID_KEY FOREIGN KEY (FromStatusId) REFERENCES Statuses (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT TRANSITION_TO_STATUS_ID_KEY FOREIGN KEY (ToStatusId) REFERENCES Statuses (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS WorkflowHooks (
+    WorkspaceId UUID NOT NULL,
+    StatusId UUID NOT NULL,
+    Hook VARCHAR(64) NOT NULL,
+    PRIMARY KEY (WorkspaceId, StatusId, Hook),
+    CONSTRAINT HOOK_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT HOOK_STATUS_ID_KEY FOREIGN KEY (StatusId) REFERENCES Statuses (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS TaskTemplates (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(256) NOT NULL,
+    CreateDate TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT Id_UNIQUE_TT UNIQUE (Id),
+    CONSTRAINT TEMPLATE_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+-- Position orders the nodes of a template depth first, so parents always come before their subtasks
+CREATE TABLE IF NOT EXISTS TaskTemplateNodes (
+    Id UUID PRIMARY KEY NOT NULL,
+    TemplateId UUID NOT NULL,
+    ParentId UUID NULL,
+    Position INTEGER NOT NULL,
+    Summary TEXT NOT NULL,
+    Description TEXT NULL,
+    Priority VARCHAR(64) NOT NULL,
+    DueInDays INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_TTN UNIQUE (Id),
+    CONSTRAINT NODE_TEMPLATE_ID_KEY FOREIGN KEY (TemplateId) REFERENCES TaskTemplates (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT NODE_PARENT_ID_KEY FOREIGN KEY (ParentId) REFERENCES TaskTemplateNodes (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS IdempotencyKeys (
+    WorkspaceId UUID NOT NULL,
+    Key VARCHAR(255) NOT NULL,
+    Fingerprint CHAR(64) NOT NULL,
+    StatusCode SMALLINT NULL,
+    ContentType VARCHAR(255) NULL,
+    Body BYTEA NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    ExpiresAt TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (WorkspaceId, Key)
 );
 
 CREATE TABLE IF NOT EXISTS Logs (
@@ -27,5 +258,23 @@ CREATE TABLE IF NOT EXISTS Logs (
 
 CREATE INDEX SEARCH ON Tasks USING GIN (to_tsvector('english', Summary || ' ' || Description));
 CREATE INDEX ROOT_TASK_ID_KEY_idx ON Tasks (RootTaskId);
+CREATE INDEX WORKSPACE_ID_KEY_idx ON Tasks (WorkspaceId);
+CREATE INDEX STATUS_ID_KEY_idx ON Tasks (StatusId);
+CREATE INDEX PRIORITY_ID_KEY_idx ON Tasks (PriorityId);
+CREATE UNIQUE INDEX STATUS_NAME_UNIQUE_idx ON Statuses (WorkspaceId, LOWER(Name));
+CREATE UNIQUE INDEX PRIORITY_NAME_UNIQUE_idx ON Priorities (WorkspaceId, LOWER(Name));
+CREATE INDEX TASK_TAGS_TAG_ID_KEY_idx ON TaskTags (TagId);
+CREATE INDEX COMMENT_TASK_ID_KEY_idx ON Comments (TaskId);
+CREATE INDEX REVISION_COMMENT_ID_KEY_idx ON CommentRevisions (CommentId);
+CREATE INDEX ATTACHMENT_TASK_ID_KEY_idx ON Attachments (TaskId);
+CREATE INDEX ATTACHMENT_HASH_idx ON Attachments (Hash);
+CREATE INDEX CHECKLIST_TASK_ID_KEY_idx ON ChecklistItems (TaskId, Position);
+CREATE INDEX DEPENDENCY_BLOCKED_ID_KEY_idx ON TaskDependencies (BlockedId);
+CREATE INDEX EMAIL_OUTBOX_PENDING_idx ON EmailOutbox (NextAttemptDate) WHERE SentDate IS NULL;
+CREATE INDEX TEMPLATE_WORKSPACE_ID_KEY_idx ON TaskTemplates (WorkspaceId);
+CREATE INDEX NODE_TEMPLATE_ID_KEY_idx ON TaskTemplateNodes (TemplateId);
+CREATE INDEX TRASH_DELETED_AT_idx ON Tasks (WorkspaceId, DeletedAt) WHERE DeletedAt IS NOT NULL;
+CREATE INDEX TASK_RANK_idx ON Tasks (WorkspaceId, RootTaskId, Rank);
+CREATE INDEX IDEMPOTENCY_EXPIRES_AT_idx ON IdempotencyKeys (ExpiresAt);
 CREATE INDEX SEARCH_ID ON Logs (EntityId);
 CREATE INDEX SEARCH_TYPE ON Logs (EntityType);
\ No newline at end of file
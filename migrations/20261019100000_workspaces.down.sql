@@ -0,0 +1,7 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS WORKSPACE_ID_KEY_idx;
+
+ALTER TABLE Tasks DROP CONSTRAINT IF EXISTS WORKSPACE_ID_KEY;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS WorkspaceId;
+
+DROP TABLE IF EXISTS Workspaces;
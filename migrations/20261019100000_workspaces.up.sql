@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS Workspaces (
+    Id UUID PRIMARY KEY NOT NULL,
+    Name VARCHAR(255) NOT NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    CONSTRAINT Id_UNIQUE_W UNIQUE (Id)
+);
+
+-- Existing tasks are moved to the default workspace
+INSERT INTO Workspaces (Id, Name) VALUES ('00000000-0000-0000-0000-000000000001', 'Default');
+
+ALTER TABLE Tasks ADD COLUMN WorkspaceId UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001';
+ALTER TABLE Tasks ALTER COLUMN WorkspaceId DROP DEFAULT;
+ALTER TABLE Tasks ADD CONSTRAINT WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION;
+
+CREATE INDEX WORKSPACE_ID_KEY_idx ON Tasks (WorkspaceId);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS TaskTags;
+
+DROP TABLE IF EXISTS Tags;
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS Tags (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(64) NOT NULL,
+    Color VARCHAR(7) NOT NULL,
+    CONSTRAINT Id_UNIQUE_TG UNIQUE (Id),
+    CONSTRAINT NAME_UNIQUE_TG UNIQUE (WorkspaceId, Name),
+    CONSTRAINT TAG_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS TaskTags (
+    TaskId UUID NOT NULL,
+    TagId UUID NOT NULL,
+    PRIMARY KEY (TaskId, TagId),
+    CONSTRAINT TASK_TAGS_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT TASK_TAGS_TAG_ID_KEY FOREIGN KEY (TagId) REFERENCES Tags (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE INDEX TASK_TAGS_TAG_ID_KEY_idx ON TaskTags (TagId);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS CommentRevisions;
+
+DROP TABLE IF EXISTS Comments;
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS Comments (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    Author VARCHAR(255) NULL,
+    Body TEXT NOT NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    UpdateDate TIMESTAMPTZ NULL,
+    CONSTRAINT Id_UNIQUE_C UNIQUE (Id),
+    CONSTRAINT COMMENT_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS CommentRevisions (
+    Id UUID PRIMARY KEY NOT NULL,
+    CommentId UUID NOT NULL,
+    Body TEXT NOT NULL,
+    CreateDate TIMESTAMPTZ NOT NULL,
+    CONSTRAINT Id_UNIQUE_CR UNIQUE (Id),
+    CONSTRAINT REVISION_COMMENT_ID_KEY FOREIGN KEY (CommentId) REFERENCES Comments (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE INDEX COMMENT_TASK_ID_KEY_idx ON Comments (TaskId);
+CREATE INDEX REVISION_COMMENT_ID_KEY_idx ON CommentRevisions (CommentId);
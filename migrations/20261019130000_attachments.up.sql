@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS Attachments (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    FileName VARCHAR(255) NOT NULL,
+    ContentType VARCHAR(255) NOT NULL,
+    Size BIGINT NOT NULL,
+    Hash CHAR(64) NOT NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    CONSTRAINT Id_UNIQUE_A UNIQUE (Id),
+    CONSTRAINT ATTACHMENT_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE INDEX ATTACHMENT_TASK_ID_KEY_idx ON Attachments (TaskId);
+CREATE INDEX ATTACHMENT_HASH_idx ON Attachments (Hash);
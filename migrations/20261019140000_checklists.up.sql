@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS ChecklistItems (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    Text VARCHAR(1024) NOT NULL,
+    Done BOOLEAN NOT NULL DEFAULT FALSE,
+    Position INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_CI UNIQUE (Id),
+    CONSTRAINT CHECKLIST_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE INDEX CHECKLIST_TASK_ID_KEY_idx ON ChecklistItems (TaskId, Position);
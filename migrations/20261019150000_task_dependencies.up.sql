@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS TaskDependencies (
+    BlockerId UUID NOT NULL,
+    BlockedId UUID NOT NULL,
+    PRIMARY KEY (BlockerId, BlockedId),
+    CONSTRAINT NO_SELF_DEPENDENCY CHECK (BlockerId <> BlockedId),
+    CONSTRAINT DEPENDENCY_BLOCKER_ID_KEY FOREIGN KEY (BlockerId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT DEPENDENCY_BLOCKED_ID_KEY FOREIGN KEY (BlockedId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE INDEX DEPENDENCY_BLOCKED_ID_KEY_idx ON TaskDependencies (BlockedId);
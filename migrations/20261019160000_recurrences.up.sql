@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS TaskRecurrences (
+    TaskId UUID PRIMARY KEY NOT NULL,
+    Rule VARCHAR(256) NOT NULL,
+    CloneSubtasks BOOLEAN NOT NULL DEFAULT FALSE,
+    Occurrence INTEGER NOT NULL DEFAULT 1,
+    PreviousTaskId UUID NULL,
+    NextTaskId UUID NULL,
+    CONSTRAINT RECURRENCE_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT RECURRENCE_PREVIOUS_TASK_ID_KEY FOREIGN KEY (PreviousTaskId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION,
+    CONSTRAINT RECURRENCE_NEXT_TASK_ID_KEY FOREIGN KEY (NextTaskId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION
+);
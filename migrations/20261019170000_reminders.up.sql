@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS FiredReminders (
+    Id UUID PRIMARY KEY NOT NULL,
+    TaskId UUID NOT NULL,
+    Kind VARCHAR(64) NOT NULL,
+    DueDate TIMESTAMPTZ NOT NULL,
+    FireDate TIMESTAMPTZ NOT NULL,
+    CONSTRAINT Id_UNIQUE_FR UNIQUE (Id),
+    CONSTRAINT FIRED_REMINDER_UNIQUE UNIQUE (TaskId, Kind, DueDate),
+    CONSTRAINT REMINDER_TASK_ID_KEY FOREIGN KEY (TaskId) REFERENCES Tasks (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS EmailOutbox;
+DROP TABLE IF EXISTS NotificationPreferences;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS Assignee;
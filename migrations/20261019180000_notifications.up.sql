@@ -0,0 +1,27 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS Assignee VARCHAR(320) NULL;
+
+CREATE TABLE IF NOT EXISTS NotificationPreferences (
+    Email VARCHAR(320) PRIMARY KEY NOT NULL,
+    Assignment BOOLEAN NOT NULL DEFAULT TRUE,
+    StatusChange BOOLEAN NOT NULL DEFAULT TRUE,
+    Comments BOOLEAN NOT NULL DEFAULT TRUE,
+    DueSoon BOOLEAN NOT NULL DEFAULT TRUE,
+    Digest BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE IF NOT EXISTS EmailOutbox (
+    Id UUID PRIMARY KEY NOT NULL,
+    Recipient VARCHAR(320) NOT NULL,
+    Subject VARCHAR(512) NOT NULL,
+    TextBody TEXT NOT NULL,
+    HtmlBody TEXT NOT NULL,
+    Digest BOOLEAN NOT NULL DEFAULT FALSE,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    Attempts INTEGER NOT NULL DEFAULT 0,
+    NextAttemptDate TIMESTAMPTZ DEFAULT NOW(),
+    SentDate TIMESTAMPTZ NULL,
+    LastError TEXT NULL,
+    CONSTRAINT Id_UNIQUE_EO UNIQUE (Id)
+);
+
+CREATE INDEX EMAIL_OUTBOX_PENDING_idx ON EmailOutbox (NextAttemptDate) WHERE SentDate IS NULL;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS WorkflowHooks;
+DROP TABLE IF EXISTS WorkflowTransitions;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS Resolution;
@@ -0,0 +1,19 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS Resolution TEXT NULL;
+
+CREATE TABLE IF NOT EXISTS WorkflowTransitions (
+    WorkspaceId UUID NOT NULL,
+    FromStatus SMALLINT NOT NULL,
+    ToStatus SMALLINT NOT NULL,
+    RequiredFields VARCHAR(64)[] NOT NULL DEFAULT '{}',
+    PRIMARY KEY (WorkspaceId, FromStatus, ToStatus),
+    CONSTRAINT NO_SELF_TRANSITION CHECK (FromStatus <> ToStatus),
+    CONSTRAINT TRANSITION_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS WorkflowHooks (
+    WorkspaceId UUID NOT NULL,
+    Status SMALLINT NOT NULL,
+    Hook VARCHAR(64) NOT NULL,
+    PRIMARY KEY (WorkspaceId, Status, Hook),
+    CONSTRAINT HOOK_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
@@ -0,0 +1,52 @@
+-- Custom statuses and priorities have no fixed value, their tasks fall back to Reserved / Low
+CREATE TEMPORARY TABLE DefaultStatuses (Code, Name) AS VALUES
+    (0::SMALLINT, 'Reserved'),
+    (1::SMALLINT, 'Ongoing'),
+    (2::SMALLINT, 'Done'),
+    (3::SMALLINT, 'Pending');
+
+CREATE TEMPORARY TABLE DefaultPriorities (Code, Name) AS VALUES
+    (0::SMALLINT, 'Low'),
+    (1::SMALLINT, 'Normal'),
+    (2::SMALLINT, 'High'),
+    (3::SMALLINT, 'Urgent');
+
+ALTER TABLE Tasks ADD COLUMN Status SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE Tasks ADD COLUMN Priority SMALLINT NOT NULL DEFAULT 0;
+
+UPDATE Tasks t SET Status = d.Code FROM Statuses s, DefaultStatuses d WHERE s.Id = t.StatusId AND d.Name = s.Name;
+UPDATE Tasks t SET Priority = d.Code FROM Priorities p, DefaultPriorities d WHERE p.Id = t.PriorityId AND d.Name = p.Name;
+
+ALTER TABLE WorkflowTransitions ADD COLUMN FromStatus SMALLINT NULL;
+ALTER TABLE WorkflowTransitions ADD COLUMN ToStatus SMALLINT NULL;
+
+UPDATE WorkflowTransitions w SET FromStatus = d.Code FROM Statuses s, DefaultStatuses d WHERE s.Id = w.FromStatusId AND d.Name = s.Name;
+UPDATE WorkflowTransitions w SET ToStatus = d.Code FROM Statuses s, DefaultStatuses d WHERE s.Id = w.ToStatusId AND d.Name = s.Name;
+DELETE FROM WorkflowTransitions WHERE FromStatus IS NULL OR ToStatus IS NULL;
+
+ALTER TABLE WorkflowTransitions DROP CONSTRAINT WorkflowTransitions_pkey;
+ALTER TABLE WorkflowTransitions DROP CONSTRAINT NO_SELF_TRANSITION;
+ALTER TABLE WorkflowTransitions DROP COLUMN FromStatusId;
+ALTER TABLE WorkflowTransitions DROP COLUMN ToStatusId;
+ALTER TABLE WorkflowTransitions ALTER COLUMN FromStatus SET NOT NULL;
+ALTER TABLE WorkflowTransitions ALTER COLUMN ToStatus SET NOT NULL;
+ALTER TABLE WorkflowTransitions ADD PRIMARY KEY (WorkspaceId, FromStatus, ToStatus);
+ALTER TABLE WorkflowTransitions ADD CONSTRAINT NO_SELF_TRANSITION CHECK (FromStatus <> ToStatus);
+
+ALTER TABLE WorkflowHooks ADD COLUMN Status SMALLINT NULL;
+
+UPDATE WorkflowHooks h SET Status = d.Code FROM Statuses s, DefaultStatuses d WHERE s.Id = h.StatusId AND d.Name = s.Name;
+DELETE FROM WorkflowHooks WHERE Status IS NULL;
+
+ALTER TABLE WorkflowHooks DROP CONSTRAINT WorkflowHooks_pkey;
+ALTER TABLE WorkflowHooks DROP COLUMN StatusId;
+ALTER TABLE WorkflowHooks ALTER COLUMN Status SET NOT NULL;
+ALTER TABLE WorkflowHooks ADD PRIMARY KEY (WorkspaceId, Status, Hook);
+
+ALTER TABLE Tasks DROP COLUMN StatusId;
+ALTER TABLE Tasks DROP COLUMN PriorityId;
+
+DROP TABLE IF EXISTS Statuses;
+DROP TABLE IF EXISTS Priorities;
+DROP TABLE DefaultStatuses;
+DROP TABLE DefaultPriorities;
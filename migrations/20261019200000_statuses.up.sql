@@ -0,0 +1,89 @@
+CREATE TABLE IF NOT EXISTS Statuses (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(64) NOT NULL,
+    Color VARCHAR(7) NOT NULL,
+    Position INTEGER NOT NULL DEFAULT 0,
+    Category SMALLINT NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_S UNIQUE (Id),
+    CONSTRAINT STATUS_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE TABLE IF NOT EXISTS Priorities (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(64) NOT NULL,
+    Color VARCHAR(7) NOT NULL,
+    Position INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_P UNIQUE (Id),
+    CONSTRAINT PRIORITY_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE UNIQUE INDEX STATUS_NAME_UNIQUE_idx ON Statuses (WorkspaceId, LOWER(Name));
+CREATE UNIQUE INDEX PRIORITY_NAME_UNIQUE_idx ON Priorities (WorkspaceId, LOWER(Name));
+
+-- Former fixed values, Code is what Tasks.Status / Tasks.Priority used to hold
+CREATE TEMPORARY TABLE DefaultStatuses (Code, Name, Color, Position, Category) AS VALUES
+    (0::SMALLINT, 'Reserved', '#9e9e9e', 0, 0::SMALLINT),
+    (1::SMALLINT, 'Ongoing', '#1e90ff', 1, 1::SMALLINT),
+    (3::SMALLINT, 'Pending', '#f9a825', 2, 0::SMALLINT),
+    (2::SMALLINT, 'Done', '#2e7d32', 3, 2::SMALLINT);
+
+CREATE TEMPORARY TABLE DefaultPriorities (Code, Name, Color, Position) AS VALUES
+    (0::SMALLINT, 'Low', '#8bc34a', 0),
+    (1::SMALLINT, 'Normal', '#1e90ff', 1),
+    (2::SMALLINT, 'High', '#ff9800', 2),
+    (3::SMALLINT, 'Urgent', '#f44336', 3);
+
+INSERT INTO Statuses (Id, WorkspaceId, Name, Color, Position, Category)
+SELECT gen_random_uuid(), w.Id, d.Name, d.Color, d.Position, d.Category FROM Workspaces w CROSS JOIN DefaultStatuses d;
+
+INSERT INTO Priorities (Id, WorkspaceId, Name, Color, Position)
+SELECT gen_random_uuid(), w.Id, d.Name, d.Color, d.Position FROM Workspaces w CROSS JOIN DefaultPriorities d;
+
+ALTER TABLE Tasks ADD COLUMN StatusId UUID NULL;
+ALTER TABLE Tasks ADD COLUMN PriorityId UUID NULL;
+
+UPDATE Tasks t SET StatusId = s.Id FROM DefaultStatuses d, Statuses s WHERE d.Code = t.Status AND s.WorkspaceId = t.WorkspaceId AND s.Name = d.Name;
+UPDATE Tasks t SET PriorityId = p.Id FROM DefaultPriorities d, Priorities p WHERE d.Code = t.Priority AND p.WorkspaceId = t.WorkspaceId AND p.Name = d.Name;
+
+ALTER TABLE Tasks ALTER COLUMN StatusId SET NOT NULL;
+ALTER TABLE Tasks ALTER COLUMN PriorityId SET NOT NULL;
+ALTER TABLE Tasks ADD CONSTRAINT STATUS_ID_KEY FOREIGN KEY (StatusId) REFERENCES Statuses (Id) ON DELETE NO ACTION ON UPDATE NO ACTION;
+ALTER TABLE Tasks ADD CONSTRAINT PRIORITY_ID_KEY FOREIGN KEY (PriorityId) REFERENCES Priorities (Id) ON DELETE NO ACTION ON UPDATE NO ACTION;
+ALTER TABLE Tasks DROP COLUMN Status;
+ALTER TABLE Tasks DROP COLUMN Priority;
+
+CREATE INDEX STATUS_ID_KEY_idx ON Tasks (StatusId);
+CREATE INDEX PRIORITY_ID_KEY_idx ON Tasks (PriorityId);
+
+-- Workflow rules point at the workspace statuses now
+ALTER TABLE WorkflowTransitions ADD COLUMN FromStatusId UUID NULL;
+ALTER TABLE WorkflowTransitions ADD COLUMN ToStatusId UUID NULL;
+
+UPDATE WorkflowTransitions w SET FromStatusId = s.Id FROM DefaultStatuses d, Statuses s WHERE d.Code = w.FromStatus AND s.WorkspaceId = w.WorkspaceId AND s.Name = d.Name;
+UPDATE WorkflowTransitions w SET ToStatusId = s.Id FROM DefaultStatuses d, Statuses s WHERE d.Code = w.ToStatus AND s.WorkspaceId = w.WorkspaceId AND s.Name = d.Name;
+
+ALTER TABLE WorkflowTransitions DROP CONSTRAINT NO_SELF_TRANSITION;
+ALTER TABLE WorkflowTransitions DROP CONSTRAINT WorkflowTransitions_pkey;
+ALTER TABLE WorkflowTransitions DROP COLUMN FromStatus;
+ALTER TABLE WorkflowTransitions DROP COLUMN ToStatus;
+ALTER TABLE WorkflowTransitions ALTER COLUMN FromStatusId SET NOT NULL;
+ALTER TABLE WorkflowTransitions ALTER COLUMN ToStatusId SET NOT NULL;
+ALTER TABLE WorkflowTransitions ADD PRIMARY KEY (WorkspaceId, FromStatusId, ToStatusId);
+ALTER TABLE WorkflowTransitions ADD CONSTRAINT NO_SELF_TRANSITION CHECK (FromStatusId <> ToStatusId);
+ALTER TABLE WorkflowTransitions ADD CONSTRAINT TRANSITION_FROM_STATUS_ID_KEY FOREIGN KEY (FromStatusId) REFERENCES Statuses (Id) ON DELETE CASCADE ON UPDATE NO ACTION;
+ALTER TABLE WorkflowTransitions ADD CONSTRAINT TRANSITION_TO_STATUS_ID_KEY FOREIGN KEY (ToStatusId) REFERENCES Statuses (Id) ON DELETE CASCADE ON UPDATE NO ACTION;
+
+ALTER TABLE WorkflowHooks ADD COLUMN StatusId UUID NULL;
+
+UPDATE WorkflowHooks h SET StatusId = s.Id FROM DefaultStatuses d, Statuses s WHERE d.Code = h.Status AND s.WorkspaceId = h.WorkspaceId AND s.Name = d.Name;
+
+ALTER TABLE WorkflowHooks DROP CONSTRAINT WorkflowHooks_pkey;
+ALTER TABLE WorkflowHooks DROP COLUMN Status;
+ALTER TABLE WorkflowHooks ALTER COLUMN StatusId SET NOT NULL;
+ALTER TABLE WorkflowHooks ADD PRIMARY KEY (WorkspaceId, StatusId, Hook);
+ALTER TABLE WorkflowHooks ADD CONSTRAINT HOOK_STATUS_ID_KEY FOREIGN KEY (StatusId) REFERENCES Statuses (Id) ON DELETE CASCADE ON UPDATE NO ACTION;
+
+DROP TABLE DefaultStatuses;
+DROP TABLE DefaultPriorities;
@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE Tasks DROP COLUMN IF EXISTS AutoComplete;
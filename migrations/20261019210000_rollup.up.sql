@@ -0,0 +1 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS AutoComplete BOOLEAN NOT NULL DEFAULT FALSE;
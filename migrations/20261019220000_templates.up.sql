@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS TaskTemplates (
+    Id UUID PRIMARY KEY NOT NULL,
+    WorkspaceId UUID NOT NULL,
+    Name VARCHAR(256) NOT NULL,
+    CreateDate TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT Id_UNIQUE_TT UNIQUE (Id),
+    CONSTRAINT TEMPLATE_WORKSPACE_ID_KEY FOREIGN KEY (WorkspaceId) REFERENCES Workspaces (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+-- Position orders the nodes of a template depth first, so parents always come before their subtasks
+CREATE TABLE IF NOT EXISTS TaskTemplateNodes (
+    Id UUID PRIMARY KEY NOT NULL,
+    TemplateId UUID NOT NULL,
+    ParentId UUID NULL,
+    Position INTEGER NOT NULL,
+    Summary TEXT NOT NULL,
+    Description TEXT NULL,
+    Priority VARCHAR(64) NOT NULL,
+    DueInDays INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT Id_UNIQUE_TTN UNIQUE (Id),
+    CONSTRAINT NODE_TEMPLATE_ID_KEY FOREIGN KEY (TemplateId) REFERENCES TaskTemplates (Id) ON DELETE CASCADE ON UPDATE NO ACTION,
+    CONSTRAINT NODE_PARENT_ID_KEY FOREIGN KEY (ParentId) REFERENCES TaskTemplateNodes (Id) ON DELETE CASCADE ON UPDATE NO ACTION
+);
+
+CREATE INDEX TEMPLATE_WORKSPACE_ID_KEY_idx ON TaskTemplates (WorkspaceId);
+CREATE INDEX NODE_TEMPLATE_ID_KEY_idx ON TaskTemplateNodes (TemplateId);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS TRASH_DELETED_AT_idx;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS DeletedBy;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS DeletedAt;
@@ -0,0 +1,4 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS DeletedAt TIMESTAMPTZ NULL;
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS DeletedBy VARCHAR(320) NULL;
+
+CREATE INDEX TRASH_DELETED_AT_idx ON Tasks (WorkspaceId, DeletedAt) WHERE DeletedAt IS NOT NULL;
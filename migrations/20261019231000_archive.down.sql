@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE Tasks DROP COLUMN IF EXISTS DoneDate;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS ArchivedAt;
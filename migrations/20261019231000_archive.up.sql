@@ -0,0 +1,8 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS ArchivedAt TIMESTAMPTZ NULL;
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS DoneDate TIMESTAMPTZ NULL;
+
+-- Tasks already done were finished when their status last changed (action 22 is StatusChanged, logs hold unix seconds), or at creation when it never did
+UPDATE Tasks t SET DoneDate = COALESCE(
+    (SELECT to_timestamp(MAX(l.TimestampMsec)) FROM Logs l WHERE l.EntityId = t.Id AND l.Action = 22),
+    t.CreateDate)
+FROM Statuses s WHERE s.Id = t.StatusId AND s.Category = 2 AND t.DoneDate IS NULL;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS TASK_RANK_idx;
+ALTER TABLE Tasks DROP COLUMN IF EXISTS Rank;
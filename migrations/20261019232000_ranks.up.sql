@@ -0,0 +1,7 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS Rank VARCHAR(255) COLLATE "C" NOT NULL DEFAULT '';
+
+UPDATE Tasks t SET Rank = r.Rank
+FROM (SELECT Id, 'i' || LPAD(TO_HEX(ROW_NUMBER() OVER (PARTITION BY WorkspaceId, RootTaskId ORDER BY CreateDate, Id)), 5, '0') AS Rank FROM Tasks) r
+WHERE r.Id = t.Id;
+
+CREATE INDEX TASK_RANK_idx ON Tasks (WorkspaceId, RootTaskId, Rank);
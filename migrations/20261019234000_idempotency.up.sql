@@ -0,0 +1,14 @@
+-- Keys are per workspace, requests outside of any workspace use the nil id
+CREATE TABLE IF NOT EXISTS IdempotencyKeys (
+    WorkspaceId UUID NOT NULL,
+    Key VARCHAR(255) NOT NULL,
+    Fingerprint CHAR(64) NOT NULL,
+    StatusCode SMALLINT NULL,
+    ContentType VARCHAR(255) NULL,
+    Body BYTEA NULL,
+    CreateDate TIMESTAMPTZ DEFAULT NOW(),
+    ExpiresAt TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (WorkspaceId, Key)
+);
+
+CREATE INDEX IDEMPOTENCY_EXPIRES_AT_idx ON IdempotencyKeys (ExpiresAt);
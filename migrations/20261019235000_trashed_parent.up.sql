@@ -0,0 +1,2 @@
+ALTER TABLE Tasks ADD COLUMN IF NOT EXISTS TrashedParentId UUID NULL;
+ALTER TABLE Tasks ADD CONSTRAINT TRASHED_PARENT_ID_KEY FOREIGN KEY (TrashedParentId) REFERENCES Tasks (Id) ON DELETE SET NULL ON UPDATE NO ACTION;
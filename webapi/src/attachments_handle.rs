@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use app::errors::Error;
+use axum::{
+    response::IntoResponse, 
+    http::{StatusCode, header}, 
+    Json, 
+    extract::{
+        State, 
+        Path, Multipart
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::view::CreateAttachmentResponse;
+
+pub async fn get_attachments(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.attachment_service().get_attachments(workspace_id, task_id).await {
+        Ok(attachments) => Ok(Json(json!(attachments))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn download_attachment(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.attachment_service().get_attachment_content(workspace_id, task_id, id).await {
+        Ok(attachment) => {
+            let disposition = format!("attachment; filename=\"{}\"", attachment.file_name.replace(['"', '\\', '\r', '\n'], "_"));
+
+            Ok(([(header::CONTENT_TYPE, attachment.content_type), (header::CONTENT_DISPOSITION, disposition)], attachment.content))
+        },
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+// Expects a multipart form with the file in the "file" field
+pub async fn upload_attachment(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    mut multipart: Multipart
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
+        let Ok(content) = field.bytes().await else {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("Attachment can't be larger than {} bytes", services.attachment_service().max_size())
+            });
+
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(error_response)));
+        };
+
+        match services.attachment_service().add_attachment(workspace_id, task_id, &file_name, &content_type, &content).await {
+            Ok(created_attachment_id) => return Ok(Json(json!(CreateAttachmentResponse::new(created_attachment_id)))),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)));
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+            },
+
+            Err(Error::TooLarge(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(error_response)));
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn delete_attachment(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.attachment_service().delete_attachment(workspace_id, task_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use app::{errors::Error, dtos::{CreateChecklistItemDto, ReorderChecklistDto}};
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::view::CreateChecklistItemResponse;
+
+pub async fn get_checklist(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.checklist_service().get_checklist(workspace_id, task_id).await {
+        Ok(checklist) => Ok(Json(json!(checklist))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn add_checklist_item(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(item_details) = serde_json::from_value::<CreateChecklistItemDto>(payload) {
+        match services.checklist_service().add_item(workspace_id, task_id, &item_details).await {
+            Ok(created_item_id) => return Ok(Json(json!(CreateChecklistItemResponse::new(created_item_id)))),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)));
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn toggle_checklist_item(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.checklist_service().toggle_item(workspace_id, task_id, id).await {
+        Ok(item) => Ok(Json(json!(item))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn reorder_checklist(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(order_details) = serde_json::from_value::<ReorderChecklistDto>(payload) {
+        match services.checklist_service().reorder_items(workspace_id, task_id, &order_details).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn remove_checklist_item(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.checklist_service().remove_item(workspace_id, task_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use app::{errors::Error, dtos::{CreateCommentDto, UpdateCommentDto}};
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path, Query
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::view::{Pagination, BatchResponse, CreateCommentResponse};
+
+pub async fn get_comments(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    pagination: Query<Pagination>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let result = services.comment_service()
+        .get_comment_batch(
+            workspace_id,
+            task_id,
+            &pagination.continuation_token().unwrap_or(0).to_string(), 
+            pagination.take().unwrap_or(20), 
+            pagination.descending().unwrap_or(false))
+        .await;
+
+    match result {
+        Ok((batch, continuation_token)) => Ok(Json(json!(BatchResponse::new(batch, continuation_token)))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn get_comment_revisions(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.comment_service().get_comment_revisions(workspace_id, task_id, id).await {
+        Ok(revisions) => Ok(Json(json!(revisions))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn create_comment(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(comment_details) = serde_json::from_value::<CreateCommentDto>(payload) {
+        match services.comment_service().create_comment(workspace_id, task_id, &comment_details).await {
+            Ok(created_comment_id) => return Ok(Json(json!(CreateCommentResponse::new(created_comment_id)))),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)));
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn update_comment(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(comment_details) = serde_json::from_value::<UpdateCommentDto>(payload) {
+        match services.comment_service().update_comment(workspace_id, task_id, id, &comment_details).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn delete_comment(
+    Path((workspace_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.comment_service().delete_comment(workspace_id, task_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
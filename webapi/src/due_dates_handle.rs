@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use app::errors::Error;
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn analyze_due_dates(
+    Path((workspace_id, id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.due_date_service().analyze(workspace_id, id).await {
+        Ok(report) => Ok(Json(json!(report))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn propagate_due_dates(
+    Path((workspace_id, id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.due_date_service().propagate(workspace_id, id).await {
+        Ok(changes) => Ok(Json(json!(changes))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
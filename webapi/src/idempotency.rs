@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use app::{errors::Error, idempotency::{IdempotencyService, IdempotencyCheck}};
+use axum::{
+    body::{Body, Bytes, HttpBody, Full, boxed},
+    extract::State,
+    http::{Request, Method, StatusCode, HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json
+};
+use infrastructure::ServiceProvider;
+use serde_json::json;
+use uuid::Uuid;
+
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED: &str = "idempotent-replayed";
+
+// Mutating requests with an `Idempotency-Key` are handled once, retries get the stored response back.
+// The state is the provider and the largest request body that is buffered for the fingerprint
+pub async fn idempotency_guard(
+    State((services, max_body_size)): State<(Arc<ServiceProvider>, usize)>,
+    request: Request<Body>,
+    next: Next<Body>
+) -> Response {
+    let mutating = matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH | Method::DELETE);
+    let key = request.headers().get(IDEMPOTENCY_KEY).map(|v| v.to_str().unwrap_or_default().trim().to_string());
+
+    let (Some(key), true) = (key, mutating) else {
+        return next.run(request).await;
+    };
+
+    let (parts, body) = request.into_parts();
+    let Some(body) = read_body(body, max_body_size).await else {
+        return fail(StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large");
+    };
+
+    let path = parts.uri.path_and_query().map(|p| p.as_str()).unwrap_or(parts.uri.path());
+    let fingerprint = IdempotencyService::fingerprint(parts.method.as_str(), path, &body);
+    let workspace_id = key_scope(parts.uri.path());
+
+    let service = services.idempotency_service();
+    match service.begin(workspace_id, &key, &fingerprint, chrono::Utc::now()).await {
+        Ok(IdempotencyCheck::New) => {},
+
+        Ok(IdempotencyCheck::Replay { status_code, content_type, body }) => return replay(status_code, content_type, body),
+
+        Ok(IdempotencyCheck::Mismatch) => return fail(StatusCode::UNPROCESSABLE_ENTITY, "Idempotency key has already been used for a different request"),
+
+        Ok(IdempotencyCheck::InProgress) => return fail(StatusCode::CONFLICT, "Request with this idempotency key is still being processed"),
+
+        Err(Error::InvalidInput(message)) => return fail(StatusCode::BAD_REQUEST, &message),
+
+        Err(err) => return fail(StatusCode::INTERNAL_SERVER_ERROR, &format!("{:?}", err))
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let (parts, body) = response.into_parts();
+
+    let Some(body) = read_body(body, usize::MAX).await else {
+        let _ = service.release(workspace_id, &key).await;
+        return fail(StatusCode::INTERNAL_SERVER_ERROR, "Response could not be read");
+    };
+
+    // Server errors are not stored, a retry gets another chance
+    if parts.status.is_server_error() {
+        let _ = service.release(workspace_id, &key).await;
+    } else {
+        let content_type = parts.headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok());
+        if service.complete(workspace_id, &key, parts.status.as_u16(), content_type, &body).await.is_err() {
+            let _ = service.release(workspace_id, &key).await;
+        }
+    }
+
+    Response::from_parts(parts, boxed(Full::from(body)))
+}
+
+// Keys belong to the workspace of the request (the `:ws` segment), requests outside of any workspace share the nil id
+fn key_scope(path: &str) -> Uuid {
+    path.strip_prefix("/api/workspaces/")
+        .and_then(|rest| rest.split('/').next())
+        .and_then(|ws| Uuid::parse_str(ws).ok())
+        .unwrap_or_default()
+}
+
+// None once the body grows over `limit` or can't be read
+async fn read_body<B: HttpBody<Data = Bytes> + Unpin>(mut body: B, limit: usize) -> Option<Bytes> {
+    let mut buffer = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.ok()?;
+        if buffer.len() + chunk.len() > limit {
+            return None;
+        }
+
+        buffer.extend_from_slice(&chunk);
+    }
+
+    Some(Bytes::from(buffer))
+}
+
+fn replay(status_code: u16, content_type: Option<String>, body: Vec<u8>) -> Response {
+    let mut response = (StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK), body).into_response();
+
+    let headers = response.headers_mut();
+    headers.remove(header::CONTENT_TYPE);
+    if let Some(content_type) = content_type.and_then(|c| HeaderValue::from_str(&c).ok()) {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+
+    response
+}
+
+fn fail(status_code: StatusCode, message: &str) -> Response {
+    let error_response = json!({
+        "status": "fail",
+        "message": message
+    });
+
+    (status_code, Json(error_response)).into_response()
+}
@@ -8,7 +8,7 @@ use uuid::Uuid;
 use crate::view::{Pagination, BatchResponse};
 
 pub async fn get_task_logs(
-    Path(id): Path<Uuid>,
+    Path((_workspace_id, id)): Path<(Uuid, Uuid)>,
     pagination: Query<Pagination>,
     State(services): State<Arc<ServiceProvider>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
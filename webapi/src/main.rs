@@ -1,21 +1,38 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
-    routing::{get, post, patch, delete},
+    routing::{get, post, patch, put, delete},
+    extract::DefaultBodyLimit,
+    middleware,
     Router,
     http::{
         header,
+        HeaderName,
         HeaderValue
     }
 };
 use dotenv::dotenv;
 use tower_http::cors::{CorsLayer, Any};
 
-use infrastructure::ServiceProvider;
+use infrastructure::{ServiceProvider, settings::Settings};
 
 pub mod tasks_handle;
 pub mod logs_handle;
+pub mod workspaces_handle;
+pub mod tags_handle;
+pub mod comments_handle;
+pub mod attachments_handle;
+pub mod checklists_handle;
+pub mod due_dates_handle;
+pub mod reminders_handle;
+pub mod notifications_handle;
+pub mod workflow_handle;
+pub mod statuses_handle;
+pub mod trees_handle;
+pub mod templates_handle;
+pub mod trash_handle;
 pub mod view;
+pub mod idempotency;
 
 #[tokio::main]
 async fn main() {
@@ -24,24 +41,157 @@ async fn main() {
     let cors = CorsLayer::new()
         .allow_origin("http://localhost:5454".parse::<HeaderValue>().unwrap())
         .allow_methods(Any)
-        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE]);
+        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE, header::IF_MATCH, header::IF_NONE_MATCH, HeaderName::from_static(idempotency::IDEMPOTENCY_KEY)])
+        .expose_headers([header::ETAG, HeaderName::from_static(idempotency::IDEMPOTENT_REPLAYED)]);
 
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let settings = Settings::from_env();
+    let services = Arc::new(ServiceProvider::new(&database_url, &settings));
+
+    // Reminder scheduler lives in the same process, fired reminders are stored so a restart doesn't repeat them
+    let reminder_service = services.reminder_service();
+    let reminder_period = Duration::from_secs(settings.reminder_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(reminder_period);
+        loop {
+            interval.tick().await;
+            reminder_service.fire_due(chrono::Utc::now()).await;
+        }
+    });
+
+    // Email outbox worker, sends queued notifications with retries and folds digests once a day
+    let notification_service = services.notification_service();
+    let notification_period = Duration::from_secs(settings.notification_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(notification_period);
+        loop {
+            interval.tick().await;
+            notification_service.send_digests(chrono::Utc::now()).await;
+            notification_service.process_outbox(chrono::Utc::now()).await;
+        }
+    });
+
+    // Trash cleanup, tasks deleted longer than the retention period ago are purged for good
+    let trash_service = services.trash_service();
+    let trash_period = Duration::from_secs(settings.trash_purge_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(trash_period);
+        loop {
+            interval.tick().await;
+            trash_service.purge_expired(chrono::Utc::now()).await;
+        }
+    });
+
+    // Auto-archiving of task trees that have been done for a while, off unless AUTO_ARCHIVE_DAYS is set
+    let archive_service = services.archive_service();
+    let archive_period = Duration::from_secs(settings.archive_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(archive_period);
+        loop {
+            interval.tick().await;
+            archive_service.archive_done_trees(chrono::Utc::now()).await;
+        }
+    });
+
+    // Idempotency keys are forgotten once their lifetime is over
+    let idempotency_service = services.idempotency_service();
+    let idempotency_period = Duration::from_secs(settings.idempotency_purge_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(idempotency_period);
+        loop {
+            interval.tick().await;
+            idempotency_service.purge_expired(chrono::Utc::now()).await;
+        }
+    });
+
+    // Leaves some room for the multipart envelope of attachments, the exact limit is checked by the service
+    let max_body_size = settings.attachment_max_size + 64 * 1024;
     
     let app = 
         Router::new()
-            .route("/api/tasks", get(tasks_handle::get_tasks_batch))
-            .route("/api/tasks", post(tasks_handle::create_task))
-            .route("/api/tasks/:id", get(tasks_handle::get_task))
-            .route("/api/tasks/:id", patch(tasks_handle::update_task))
-            .route("/api/tasks/:id", delete(tasks_handle::delete_task))
-            .route("/api/tasks/search/:phrase", get(tasks_handle::search_tasks))
-            .route("/api/tasks/:id/root", patch(tasks_handle::change_task_root))
-
-            .route("/api/tasks/:id/logs", get(logs_handle::get_task_logs))
+            .route("/api/workspaces", get(workspaces_handle::get_workspaces))
+            .route("/api/workspaces", post(workspaces_handle::create_workspace))
+            .route("/api/workspaces/:ws", get(workspaces_handle::get_workspace))
+            .route("/api/workspaces/:ws", patch(workspaces_handle::update_workspace))
+            .route("/api/workspaces/:ws", delete(workspaces_handle::delete_workspace))
+            .route("/api/workspaces/:ws/workflow", get(workflow_handle::get_workflow))
+            .route("/api/workspaces/:ws/workflow", put(workflow_handle::update_workflow))
+            .route("/api/workspaces/:ws/statuses", get(statuses_handle::get_statuses))
+            .route("/api/workspaces/:ws/statuses", post(statuses_handle::create_status))
+            .route("/api/workspaces/:ws/statuses/:id", patch(statuses_handle::update_status))
+            .route("/api/workspaces/:ws/statuses/:id", delete(statuses_handle::delete_status))
+            .route("/api/workspaces/:ws/priorities", get(statuses_handle::get_priorities))
+            .route("/api/workspaces/:ws/priorities", post(statuses_handle::create_priority))
+            .route("/api/workspaces/:ws/priorities/:id", patch(statuses_handle::update_priority))
+            .route("/api/workspaces/:ws/priorities/:id", delete(statuses_handle::delete_priority))
+
+            .route("/api/workspaces/:ws/tasks", get(tasks_handle::get_tasks_batch))
+            .route("/api/workspaces/:ws/tasks", post(tasks_handle::create_task))
+            .route("/api/workspaces/:ws/tasks/:id", get(tasks_handle::get_task))
+            .route("/api/workspaces/:ws/tasks/:id", patch(tasks_handle::update_task))
+            .route("/api/workspaces/:ws/tasks/:id", delete(tasks_handle::delete_task))
+            .route("/api/workspaces/:ws/tasks/search/:phrase", get(tasks_handle::search_tasks))
+            .route("/api/workspaces/:ws/tasks/bulk", post(tasks_handle::run_bulk))
+            .route("/api/workspaces/:ws/tasks/:id/root", patch(tasks_handle::change_task_root))
+            .route("/api/workspaces/:ws/tasks/:id/move", post(tasks_handle::move_task))
+            .route("/api/workspaces/:ws/tasks/:id/tags/:tag_id", put(tasks_handle::add_task_tag))
+            .route("/api/workspaces/:ws/tasks/:id/tags/:tag_id", delete(tasks_handle::remove_task_tag))
+            .route("/api/workspaces/:ws/tasks/:id/blockers/:blocker_id", put(tasks_handle::add_task_blocker))
+            .route("/api/workspaces/:ws/tasks/:id/blockers/:blocker_id", delete(tasks_handle::remove_task_blocker))
+            .route("/api/workspaces/:ws/tasks/:id/assignee", put(tasks_handle::set_task_assignee))
+            .route("/api/workspaces/:ws/tasks/:id/recurrence", put(tasks_handle::set_task_recurrence))
+            .route("/api/workspaces/:ws/tasks/:id/recurrence", delete(tasks_handle::remove_task_recurrence))
+            .route("/api/workspaces/:ws/tasks/:id/duplicate", post(tasks_handle::duplicate_task))
+            .route("/api/workspaces/:ws/tasks/:id/tree", get(trees_handle::get_task_tree))
+            .route("/api/workspaces/:ws/tasks/:id/rollup", put(tasks_handle::set_task_rollup))
+            .route("/api/workspaces/:ws/tasks/:id/archive", put(tasks_handle::archive_task))
+            .route("/api/workspaces/:ws/tasks/:id/archive", delete(tasks_handle::unarchive_task))
+            .route("/api/workspaces/:ws/tasks/:id/reminders", get(reminders_handle::get_reminders))
+            .route("/api/workspaces/:ws/tasks/:id/due-dates", get(due_dates_handle::analyze_due_dates))
+            .route("/api/workspaces/:ws/tasks/:id/due-dates/propagate", post(due_dates_handle::propagate_due_dates))
+
+            .route("/api/workspaces/:ws/tasks/:id/comments", get(comments_handle::get_comments))
+            .route("/api/workspaces/:ws/tasks/:id/comments", post(comments_handle::create_comment))
+            .route("/api/workspaces/:ws/tasks/:id/comments/:comment_id", patch(comments_handle::update_comment))
+            .route("/api/workspaces/:ws/tasks/:id/comments/:comment_id", delete(comments_handle::delete_comment))
+            .route("/api/workspaces/:ws/tasks/:id/comments/:comment_id/revisions", get(comments_handle::get_comment_revisions))
+
+            .route("/api/workspaces/:ws/tasks/:id/attachments", get(attachments_handle::get_attachments))
+            .route("/api/workspaces/:ws/tasks/:id/attachments", post(attachments_handle::upload_attachment).layer(DefaultBodyLimit::max(max_body_size)))
+            .route("/api/workspaces/:ws/tasks/:id/attachments/:attachment_id", get(attachments_handle::download_attachment))
+            .route("/api/workspaces/:ws/tasks/:id/attachments/:attachment_id", delete(attachments_handle::delete_attachment))
+
+            .route("/api/workspaces/:ws/tasks/:id/checklist", get(checklists_handle::get_checklist))
+            .route("/api/workspaces/:ws/tasks/:id/checklist", post(checklists_handle::add_checklist_item))
+            .route("/api/workspaces/:ws/tasks/:id/checklist/order", put(checklists_handle::reorder_checklist))
+            .route("/api/workspaces/:ws/tasks/:id/checklist/:item_id", delete(checklists_handle::remove_checklist_item))
+            .route("/api/workspaces/:ws/tasks/:id/checklist/:item_id/toggle", post(checklists_handle::toggle_checklist_item))
+
+            .route("/api/workspaces/:ws/templates", get(templates_handle::get_templates))
+            .route("/api/workspaces/:ws/templates", post(templates_handle::create_template))
+            .route("/api/workspaces/:ws/templates/:id", get(templates_handle::get_template))
+            .route("/api/workspaces/:ws/templates/:id", patch(templates_handle::update_template))
+            .route("/api/workspaces/:ws/templates/:id", delete(templates_handle::delete_template))
+            .route("/api/workspaces/:ws/templates/:id/instantiate", post(templates_handle::instantiate_template))
+
+            .route("/api/workspaces/:ws/trash", get(trash_handle::get_trash))
+            .route("/api/workspaces/:ws/trash/:id", delete(trash_handle::purge_task))
+            .route("/api/workspaces/:ws/trash/:id/restore", post(trash_handle::restore_task))
+
+            .route("/api/workspaces/:ws/tags", get(tags_handle::get_tags))
+            .route("/api/workspaces/:ws/tags", post(tags_handle::create_tag))
+            .route("/api/workspaces/:ws/tags/:id", get(tags_handle::get_tag))
+            .route("/api/workspaces/:ws/tags/:id", patch(tags_handle::update_tag))
+            .route("/api/workspaces/:ws/tags/:id", delete(tags_handle::delete_tag))
+
+            .route("/api/notifications/preferences/:email", get(notifications_handle::get_preferences))
+            .route("/api/notifications/preferences/:email", put(notifications_handle::update_preferences))
+
+            .route("/api/workspaces/:ws/tasks/:id/logs", get(logs_handle::get_task_logs))
             .route("/api/tasks/logs", get(logs_handle::get_all_logs))
 
-            .with_state(Arc::new(ServiceProvider::new(&database_url)))
+            .with_state(services.clone())
+            .layer(middleware::from_fn_with_state((services, max_body_size), idempotency::idempotency_guard))
             .layer(cors);
 
     axum::Server::bind(&"0.0.0.0:3005".parse().unwrap())
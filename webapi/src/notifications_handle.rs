@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use app::{errors::Error, dtos::NotificationPreferencesDto};
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::{json, Value};
+
+pub async fn get_preferences(
+    Path(email): Path<String>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let preferences = services.notification_service().get_preferences(&email.to_lowercase()).await;
+
+    Ok(Json(json!(preferences)))
+}
+
+pub async fn update_preferences(
+    Path(email): Path<String>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(preferences) = serde_json::from_value::<NotificationPreferencesDto>(payload) {
+        match services.notification_service().update_preferences(&email, &preferences).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use app::errors::Error;
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn get_reminders(
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.reminder_service().get_reminders(workspace_id, task_id).await {
+        Ok(reminders) => Ok(Json(json!(reminders))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
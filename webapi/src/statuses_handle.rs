@@ -0,0 +1,309 @@
+use std::sync::Arc;
+
+use app::{errors::Error, dtos::{UpsertStatusDto, UpsertPriorityDto}};
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::{json, Value};
+
+use crate::view::{CreateStatusResponse, CreatePriorityResponse};
+
+pub async fn get_statuses(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let statuses = services.status_service().get_statuses(workspace_id).await;
+
+    Ok(Json(json!(statuses)))
+}
+
+pub async fn create_status(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(status_details) = serde_json::from_value::<UpsertStatusDto>(payload) {
+        match services.status_service().create_status(workspace_id, &status_details).await {
+            Ok(created_status_id) => return Ok(Json(json!(CreateStatusResponse::new(created_status_id)))),
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::Conflict(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::CONFLICT, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn update_status(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(status_details) = serde_json::from_value::<UpsertStatusDto>(payload) {
+        match services.status_service().update_status(workspace_id, id, &status_details).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::Conflict(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::CONFLICT, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+// Only a status no task uses can be deleted
+pub async fn delete_status(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.status_service().delete_status(workspace_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(Error::Conflict(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::CONFLICT, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn get_priorities(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let priorities = services.status_service().get_priorities(workspace_id).await;
+
+    Ok(Json(json!(priorities)))
+}
+
+pub async fn create_priority(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(priority_details) = serde_json::from_value::<UpsertPriorityDto>(payload) {
+        match services.status_service().create_priority(workspace_id, &priority_details).await {
+            Ok(created_priority_id) => return Ok(Json(json!(CreatePriorityResponse::new(created_priority_id)))),
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::Conflict(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::CONFLICT, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn update_priority(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(priority_details) = serde_json::from_value::<UpsertPriorityDto>(payload) {
+        match services.status_service().update_priority(workspace_id, id, &priority_details).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::Conflict(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::CONFLICT, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn delete_priority(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.status_service().delete_priority(workspace_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(Error::Conflict(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::CONFLICT, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use app::{errors::Error, dtos::UpsertTagDto};
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::{json, Value};
+
+use crate::view::CreateTagResponse;
+
+pub async fn get_tags(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let tags = services.tag_service().get_tags(workspace_id).await;
+
+    Ok(Json(json!(tags)))
+}
+
+pub async fn get_tag(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.tag_service().get_tag(workspace_id, id).await {
+        Ok(tag) => Ok(Json(json!(tag))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "error",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn create_tag(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(tag_details) = serde_json::from_value::<UpsertTagDto>(payload) {
+        match services.tag_service().create_tag(workspace_id, &tag_details).await {
+            Ok(created_tag_id) => return Ok(Json(json!(CreateTagResponse::new(created_tag_id)))),
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn update_tag(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(tag_details) = serde_json::from_value::<UpsertTagDto>(payload) {
+        match services.tag_service().update_tag(workspace_id, id, &tag_details).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn delete_tag(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.tag_service().delete_tag(workspace_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
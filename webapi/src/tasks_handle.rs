@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
-use app::{errors::Error, dtos::UpsertTaskDto};
+use app::{errors::Error, dtos::{UpsertTaskDto, UpsertRecurrenceDto, AssigneeDto, RollupDto, DuplicateTaskDto, MoveTaskDto, BulkRequestDto, TaskPatchDto}};
 use axum::{
     response::IntoResponse, 
-    http::StatusCode, 
+    http::{StatusCode, HeaderMap, header}, 
     Json, 
     extract::{
         State, 
@@ -13,19 +13,25 @@ use axum::{
 use infrastructure::ServiceProvider;
 use serde_json::{json, Value};
 
-use crate::view::{Pagination, BatchResponse, CreateTaskResponse, TaskRootChangeRequest};
+use crate::view::{Pagination, BatchResponse, CreateTaskResponse, TaskRootChangeRequest, TaskFilterQuery, DeleteTaskQuery, etag, if_match, none_match};
 
 pub async fn get_task(
-    Path(id): Path<uuid::Uuid>,
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
     State(services): State<Arc<ServiceProvider>>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let result = services.task_service().get_task(id).await;
+    let result = services.task_service().get_task(workspace_id, id).await;
 
     match result {
         Ok(task) => {
             let task_response = json!(task);
 
-            return Ok(Json(task_response));
+            let current = etag(task.version(), &task_response);
+            if none_match(&headers, &current) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, current)]).into_response());
+            }
+
+            Ok(([(header::ETAG, current)], Json(task_response)).into_response())
         }
 
         Err(Error::EntityNotFound(err)) => {
@@ -33,27 +39,40 @@ pub async fn get_task(
                 "status": "fail",
                 "message": err
             });
-            return Err((StatusCode::NOT_FOUND, Json(error_response)));
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
         }
         
         Err(e) => {
-            return Err((
+            Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({"status": "error","message": format!("{:?}", e)})),
-            ));
+            ))
         }
-    };
+    }
 }
 
 pub async fn get_tasks_batch(
+    Path(workspace_id): Path<uuid::Uuid>,
     pagination: Query<Pagination>,
+    task_filter: Query<TaskFilterQuery>,
     State(services): State<Arc<ServiceProvider>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let Ok(filter) = task_filter.to_filter() else {
+        let error_response = serde_json::json!({
+            "status": "fail",
+            "message": "Invalid tag filter"
+        });
+
+        return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    };
+
     let (batch, continuation_token) = services.task_service()
         .get_root_task_batch(
+            workspace_id,
+            &filter,
             pagination.take().unwrap_or(20), 
             &pagination.continuation_token().unwrap_or(0).to_string(), 
-            &pagination.order_by().unwrap_or("CreateDate"), 
+            pagination.order_by().unwrap_or("CreateDate"), 
             pagination.descending_sort().unwrap_or(false))
         .await;
 
@@ -61,12 +80,24 @@ pub async fn get_tasks_batch(
 }
 
 pub async fn search_tasks(
-    phrase: Path<String>,
+    Path((workspace_id, phrase)): Path<(uuid::Uuid, String)>,
     pagination: Query<Pagination>,
+    task_filter: Query<TaskFilterQuery>,
     State(services): State<Arc<ServiceProvider>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let Ok(filter) = task_filter.to_filter() else {
+        let error_response = serde_json::json!({
+            "status": "fail",
+            "message": "Invalid tag filter"
+        });
+
+        return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    };
+
     let (batch, continuation_token) = services.task_service()
         .search_tasks(
+            workspace_id,
+            &filter,
             &phrase, 
             pagination.take().unwrap_or(20), 
             &pagination.continuation_token().unwrap_or(0).to_string())
@@ -76,13 +107,32 @@ pub async fn search_tasks(
 }
 
 pub async fn create_task(
+    Path(workspace_id): Path<uuid::Uuid>,
     State(services): State<Arc<ServiceProvider>>,
     Json(payload): Json<Value>
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     if let Ok(task_details)  = serde_json::from_value::<UpsertTaskDto>(payload) {
-        match services.task_service().create_task(&task_details).await {
+        match services.task_service().create_task(workspace_id, &task_details).await {
             Ok(created_task_id) => return Ok(Json(json!(CreateTaskResponse::new(created_task_id)))),
 
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)));
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
             Err(Error::DbError(message)) => {
                 let error_response = serde_json::json!({
                     "status": "fail",
@@ -108,18 +158,48 @@ pub async fn create_task(
         "message": "Invalid input"
     });
 
-    return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
 }
 
+// The body is a JSON merge patch, `application/json` and `application/merge-patch+json` are both taken as one
 pub async fn update_task(
-    Path(id): Path<uuid::Uuid>,
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
     State(services): State<Arc<ServiceProvider>>,
+    headers: HeaderMap,
     Json(payload): Json<Value>
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    if let Ok(task_details)  = serde_json::from_value::<UpsertTaskDto>(payload) {
-        match services.task_service().update_task(id, &task_details).await {
+    let content_type = headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    if content_type.starts_with("application/json-patch+json") {
+        let error_response = serde_json::json!({
+            "status": "fail",
+            "message": "JSON Patch is not supported, send a merge patch instead"
+        });
+
+        return Err((StatusCode::UNSUPPORTED_MEDIA_TYPE, Json(error_response)))
+    }
+
+    if let Ok(task_patch) = serde_json::from_value::<TaskPatchDto>(payload) {
+        match services.task_service().update_task(workspace_id, id, &task_patch, if_match(&headers).as_ref()).await {
             Ok(()) => return Ok(StatusCode::NO_CONTENT),
 
+            Err(Error::PreconditionFailed(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PRECONDITION_FAILED, Json(error_response)))
+            },
+
+            Err(Error::PreconditionRequired(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PRECONDITION_REQUIRED, Json(error_response)))
+            },
+
             Err(Error::EntityNotFound(message)) => {
                 let error_response = serde_json::json!({
                     "status": "fail",
@@ -129,6 +209,33 @@ pub async fn update_task(
                 return Err((StatusCode::NOT_FOUND, Json(error_response)))
             },
 
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::Conflict(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::CONFLICT, Json(error_response)))
+            },
+
+            Err(Error::InvalidTransition(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(error_response)))
+            },
+
             Err(err) => {
                 let error_response = serde_json::json!({
                     "status": "fail",
@@ -144,19 +251,210 @@ pub async fn update_task(
         "status": "fail",
         "message": "Invalid input"
     });
-    return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
 }
 
 pub async fn change_task_root(
-    Path(id): Path<uuid::Uuid>,
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
     State(services): State<Arc<ServiceProvider>>,
+    headers: HeaderMap,
     Json(payload): Json<Value>
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     if let Ok(change_root_request)  = serde_json::from_value::<TaskRootChangeRequest>(payload) {
-        match services.task_service().update_task_root(id, change_root_request.root_id()).await {
+        match services.task_service().update_task_root(workspace_id, id, change_root_request.root_id(), if_match(&headers).as_ref()).await {
             
             Ok(()) => return Ok(StatusCode::NO_CONTENT),
 
+            Err(Error::PreconditionFailed(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PRECONDITION_FAILED, Json(error_response)))
+            },
+
+            Err(Error::PreconditionRequired(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PRECONDITION_REQUIRED, Json(error_response)))
+            },
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidRootBinding(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn run_bulk(
+    Path(workspace_id): Path<uuid::Uuid>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(bulk_request) = serde_json::from_value::<BulkRequestDto>(payload) {
+        match services.task_service().run_bulk(workspace_id, &bulk_request).await {
+            Ok(summary) => return Ok(Json(json!(summary))),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::TooLarge(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn move_task(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    headers: HeaderMap,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(move_details) = serde_json::from_value::<MoveTaskDto>(payload) {
+        match services.task_service().move_task(workspace_id, id, &move_details, if_match(&headers).as_ref()).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::PreconditionFailed(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PRECONDITION_FAILED, Json(error_response)))
+            },
+
+            Err(Error::PreconditionRequired(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::PRECONDITION_REQUIRED, Json(error_response)))
+            },
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidRootBinding(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn duplicate_task(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(duplicate_details) = serde_json::from_value::<DuplicateTaskDto>(payload) {
+        match services.task_service().duplicate_task(workspace_id, id, &duplicate_details).await {
+            Ok(created_task_id) => return Ok(Json(json!(CreateTaskResponse::new(created_task_id)))),
+
             Err(Error::EntityNotFound(message)) => {
                 let error_response = serde_json::json!({
                     "status": "fail",
@@ -166,6 +464,24 @@ pub async fn change_task_root(
                 return Err((StatusCode::NOT_FOUND, Json(error_response)))
             },
 
+            Err(Error::InvalidRootBinding(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
             Err(err) => {
                 let error_response = serde_json::json!({
                     "status": "fail",
@@ -181,16 +497,36 @@ pub async fn change_task_root(
         "status": "fail",
         "message": "Invalid input"
     });
-    return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
 }
 
 pub async fn delete_task(
-    Path(id): Path<uuid::Uuid>,
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    query: Query<DeleteTaskQuery>,
     State(services): State<Arc<ServiceProvider>>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match services.task_service().delete_task(id).await {
+    match services.task_service().delete_task(workspace_id, id, query.mode(), query.deleted_by(), if_match(&headers).as_ref()).await {
         
-        Ok(()) => return Ok(StatusCode::NO_CONTENT),
+        Ok(summary) => Ok(Json(json!(summary))),
+
+        Err(Error::PreconditionFailed(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::PRECONDITION_FAILED, Json(error_response)))
+        },
+
+        Err(Error::PreconditionRequired(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::PRECONDITION_REQUIRED, Json(error_response)))
+        },
 
         Err(Error::EntityNotFound(message)) => {
             let error_response = serde_json::json!({
@@ -198,7 +534,16 @@ pub async fn delete_task(
                 "message": message
             });
 
-            return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(Error::InvalidInput(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::BAD_REQUEST, Json(error_response)))
         },
         
         Err(err) => {
@@ -207,7 +552,340 @@ pub async fn delete_task(
                 "message": format!("{:?}", err)
             });
 
-            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
         }
     }
-}
\ No newline at end of file
+}
+
+pub async fn add_task_tag(
+    Path((workspace_id, id, tag_id)): Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.task_service().add_tag(workspace_id, id, tag_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn remove_task_tag(
+    Path((workspace_id, id, tag_id)): Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.task_service().remove_tag(workspace_id, id, tag_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn add_task_blocker(
+    Path((workspace_id, id, blocker_id)): Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.task_service().add_blocker(workspace_id, id, blocker_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(Error::InvalidInput(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::BAD_REQUEST, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn remove_task_blocker(
+    Path((workspace_id, id, blocker_id)): Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.task_service().remove_blocker(workspace_id, id, blocker_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn set_task_recurrence(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(recurrence_details) = serde_json::from_value::<UpsertRecurrenceDto>(payload) {
+        match services.recurrence_service().set_recurrence(workspace_id, id, &recurrence_details).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn remove_task_recurrence(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.recurrence_service().remove_recurrence(workspace_id, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn set_task_assignee(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(assignee_details) = serde_json::from_value::<AssigneeDto>(payload) {
+        match services.task_service().set_assignee(workspace_id, id, assignee_details.assignee.as_deref()).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn set_task_rollup(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+    Json(payload): Json<Value>
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if let Ok(rollup_details) = serde_json::from_value::<RollupDto>(payload) {
+        match services.task_service().set_auto_complete(workspace_id, id, rollup_details.auto_complete).await {
+            Ok(()) => return Ok(StatusCode::NO_CONTENT),
+
+            Err(Error::EntityNotFound(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::NOT_FOUND, Json(error_response)))
+            },
+
+            Err(Error::InvalidInput(message)) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": message
+                });
+
+                return Err((StatusCode::BAD_REQUEST, Json(error_response)))
+            },
+
+            Err(err) => {
+                let error_response = serde_json::json!({
+                    "status": "fail",
+                    "message": format!("{:?}", err)
+                });
+
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+            }
+        }
+    }
+
+    let error_response = serde_json::json!({
+        "status": "fail",
+        "message": "Invalid input"
+    });
+    Err((StatusCode::BAD_REQUEST, Json(error_response)))
+}
+
+pub async fn archive_task(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.archive_service().archive(workspace_id, id, chrono::Utc::now()).await {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn unarchive_task(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.archive_service().unarchive(workspace_id, id).await {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use app::errors::Error;
+use axum::{
+    response::IntoResponse,
+    http::StatusCode,
+    Json,
+    extract::{
+        State,
+        Path,
+        Query
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::json;
+
+use crate::view::{Pagination, BatchResponse};
+
+pub async fn get_trash(
+    Path(workspace_id): Path<uuid::Uuid>,
+    pagination: Query<Pagination>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let (batch, continuation_token) = services
+        .trash_service()
+        .get_trash(
+            workspace_id,
+            pagination.take().unwrap_or(20),
+            &pagination.continuation_token().unwrap_or(0).to_string())
+        .await;
+
+    Ok(Json(json!(BatchResponse::new(batch, continuation_token))))
+}
+
+pub async fn restore_task(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.trash_service().restore(workspace_id, id).await {
+        Ok(summary) => Ok(Json(json!(summary))),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
+
+pub async fn purge_task(
+    Path((workspace_id, id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match services.trash_service().purge(workspace_id, id).await {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use app::errors::Error;
+use axum::{
+    response::IntoResponse, 
+    http::StatusCode, 
+    Json, 
+    extract::{
+        State, 
+        Path, Query
+    }
+};
+use infrastructure::ServiceProvider;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::view::TreeQuery;
+
+pub async fn get_task_tree(
+    Path((workspace_id, id)): Path<(Uuid, Uuid)>,
+    query: Query<TreeQuery>,
+    State(services): State<Arc<ServiceProvider>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let tree_service = services.tree_service();
+    let result = if query.flat() {
+        tree_service.get_flat_tree(workspace_id, id, query.depth(), &query.statuses()).await.map(|items| json!(items))
+    } else {
+        tree_service.get_tree(workspace_id, id, query.depth(), &query.statuses()).await.map(|node| json!(node))
+    };
+
+    match result {
+        Ok(tree) => Ok(Json(tree)),
+
+        Err(Error::EntityNotFound(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::NOT_FOUND, Json(error_response)))
+        },
+
+        Err(Error::InvalidInput(message)) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": message
+            });
+
+            Err((StatusCode::BAD_REQUEST, Json(error_response)))
+        },
+
+        Err(err) => {
+            let error_response = serde_json::json!({
+                "status": "fail",
+                "message": format!("{:?}", err)
+            });
+
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)))
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use app::{repos::TaskFilter, dtos::{DeleteMode, ExpectedVersion}};
+use axum::http::{HeaderMap, header};
 use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 #[derive(Deserialize)]
@@ -24,6 +27,53 @@ impl Pagination {
     pub fn descending(&self) -> Option<bool> { self.descending }
 }
 
+#[derive(Deserialize)]
+pub struct TaskFilterQuery {
+    tags: Option<String>, // comma separated tag ids
+    tags_match: Option<String>, // "any" (default) or "all"
+    include_archived: Option<bool>
+}
+
+impl TaskFilterQuery {
+    pub fn to_filter(&self) -> Result<TaskFilter, uuid::Error> {
+        let tag_ids = match self.tags.as_deref() {
+            Some(tags) => tags.split(',').filter(|t| !t.is_empty()).map(Uuid::parse_str).collect::<Result<Vec<Uuid>, _>>()?,
+            None => vec![]
+        };
+
+        Ok(TaskFilter { tag_ids, match_all_tags: self.tags_match.as_deref() == Some("all"), include_archived: self.include_archived.unwrap_or(false) })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DeleteTaskQuery {
+    mode: Option<DeleteMode>, // "orphan" when omitted, as deletes always used to work
+    deleted_by: Option<String> // email, kept with the trashed tasks
+}
+
+impl DeleteTaskQuery {
+    pub fn mode(&self) -> DeleteMode { self.mode.unwrap_or(DeleteMode::Orphan) }
+    pub fn deleted_by(&self) -> Option<&str> { self.deleted_by.as_deref() }
+}
+
+#[derive(Deserialize)]
+pub struct TreeQuery {
+    depth: Option<i32>, // levels below the task, no limit when omitted
+    status: Option<String>, // comma separated status names
+    flat: Option<bool>
+}
+
+impl TreeQuery {
+    pub fn depth(&self) -> Option<i32> { self.depth }
+    pub fn statuses(&self) -> Vec<String> {
+        match self.status.as_deref() {
+            Some(status) => status.split(',').filter(|s| !s.trim().is_empty()).map(|s| s.to_string()).collect(),
+            None => vec![]
+        }
+    }
+    pub fn flat(&self) -> bool { self.flat.unwrap_or(false) }
+}
+
 #[derive(Debug, Serialize)]
 pub struct BatchResponse<T> {
     entities: Vec<T>,
@@ -53,4 +103,179 @@ pub struct TaskRootChangeRequest {
 
 impl TaskRootChangeRequest {
     pub fn root_id(&self) -> Option<Uuid> { self.root_id }
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct CreateWorkspaceResponse {
+    workspace_id: Uuid
+}
+
+impl CreateWorkspaceResponse {
+    pub fn new(workspace_id: Uuid) -> CreateWorkspaceResponse { CreateWorkspaceResponse { workspace_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreateTagResponse {
+    tag_id: Uuid
+}
+
+impl CreateTagResponse {
+    pub fn new(tag_id: Uuid) -> CreateTagResponse { CreateTagResponse { tag_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreateStatusResponse {
+    status_id: Uuid
+}
+
+impl CreateStatusResponse {
+    pub fn new(status_id: Uuid) -> CreateStatusResponse { CreateStatusResponse { status_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreatePriorityResponse {
+    priority_id: Uuid
+}
+
+impl CreatePriorityResponse {
+    pub fn new(priority_id: Uuid) -> CreatePriorityResponse { CreatePriorityResponse { priority_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreateCommentResponse {
+    comment_id: Uuid
+}
+
+impl CreateCommentResponse {
+    pub fn new(comment_id: Uuid) -> CreateCommentResponse { CreateCommentResponse { comment_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreateAttachmentResponse {
+    attachment_id: Uuid
+}
+
+impl CreateAttachmentResponse {
+    pub fn new(attachment_id: Uuid) -> CreateAttachmentResponse { CreateAttachmentResponse { attachment_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreateChecklistItemResponse {
+    item_id: Uuid
+}
+
+impl CreateChecklistItemResponse {
+    pub fn new(item_id: Uuid) -> CreateChecklistItemResponse { CreateChecklistItemResponse { item_id } }
+}
+
+#[derive(Serialize)]
+pub struct CreateTemplateResponse {
+    template_id: Uuid
+}
+
+impl CreateTemplateResponse {
+    pub fn new(template_id: Uuid) -> CreateTemplateResponse { CreateTemplateResponse { template_id } }
+}
+
+// A task ETag is its version followed by a hash of the whole representation, since tags, checklist, subtasks and the rest
+// change without touching the version of the task
+pub fn etag(version: i32, representation: &serde_json::Value) -> String {
+    let hash = Sha256::digest(representation.to_string().as_bytes());
+    format!("\"{}-{}\"", version, &hex::encode(hash)[..16])
+}
+
+// `If-Match` compares strongly, so weak and foreign tags are dropped and can never match. Writes only care about the task
+// itself, so just the version part of the tag is compared
+pub fn if_match(headers: &HeaderMap) -> Option<ExpectedVersion> {
+    let value = headers.get(header::IF_MATCH)?.to_str().unwrap_or_default();
+    if value.trim() == "*" {
+        return Some(ExpectedVersion::Any);
+    }
+
+    let versions = value.split(',')
+        .filter_map(|tag| tag.trim().strip_prefix('"')?.strip_suffix('"')?.split('-').next()?.parse::<i32>().ok())
+        .collect();
+
+    Some(ExpectedVersion::OneOf(versions))
+}
+
+// `If-None-Match` compares weakly, a `W/` prefix is ignored
+pub fn none_match(headers: &HeaderMap, current: &str) -> bool {
+    let Some(value) = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    value.split(',').map(|tag| tag.trim()).any(|tag| tag == "*" || tag.trim_start_matches("W/") == current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn etag_changes_with_version_and_representation() {
+        let task = serde_json::json!({"summary": "a", "tags": []});
+        let tagged = serde_json::json!({"summary": "a", "tags": ["x"]});
+
+        assert!(etag(3, &task).starts_with("\"3-"));
+        assert_eq!(etag(3, &task), etag(3, &task));
+        assert_ne!(etag(3, &task), etag(4, &task));
+        assert_ne!(etag(3, &task), etag(3, &tagged));
+    }
+
+    #[test]
+    fn if_match_reads_versions() {
+        let task = serde_json::json!({});
+        let cases = [
+            ("*", ExpectedVersion::Any),
+            (" * ", ExpectedVersion::Any),
+            ("\"3\"", ExpectedVersion::OneOf(vec![3])),
+            (&etag(3, &task), ExpectedVersion::OneOf(vec![3])),
+            (&format!("\"1\", {} ,\"7-abc\"", etag(3, &task)), ExpectedVersion::OneOf(vec![1, 3, 7])),
+            // Weak tags can't be compared strongly
+            ("W/\"3\"", ExpectedVersion::OneOf(vec![])),
+            ("W/\"1\", \"2\"", ExpectedVersion::OneOf(vec![2])),
+            // Malformed tags never match
+            ("3", ExpectedVersion::OneOf(vec![])),
+            ("\"abc\"", ExpectedVersion::OneOf(vec![])),
+            ("\"3", ExpectedVersion::OneOf(vec![])),
+            ("\"\"", ExpectedVersion::OneOf(vec![])),
+            ("*, \"3\"", ExpectedVersion::OneOf(vec![3])),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(if_match(&headers(header::IF_MATCH, value)), Some(expected), "{}", value);
+        }
+
+        assert_eq!(if_match(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn none_match_compares_weakly() {
+        let current = etag(3, &serde_json::json!({}));
+        let cases = [
+            ("*", true),
+            (current.as_str(), true),
+            (&format!("W/{}", current), true),
+            (&format!("\"1-abc\", {}", current), true),
+            (&format!("\"1-abc\",W/{}", current), true),
+            ("\"3\"", false),
+            ("\"1-abc\"", false),
+            (current.trim_matches('"'), false),
+            ("", false),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(none_match(&headers(header::IF_NONE_MATCH, value), &current), expected, "{}", value);
+        }
+
+        assert!(!none_match(&HeaderMap::new(), &current));
+    }
+}